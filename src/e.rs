@@ -0,0 +1,47 @@
+//! error
+//!
+
+/// Error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+  /// singular (or nearly singular within prec)
+  Singular,
+  /// not positive definite
+  NotPositiveDefinite,
+  /// no real principal logarithm
+  NoRealLog,
+  /// homogeneous point at infinity (w = 0)
+  AtInfinity,
+  /// not square (rows, cols)
+  NotSquare(usize, usize),
+  /// dimension mismatch
+  DimensionMismatch {
+    /// expected size
+    expected: usize,
+    /// actual size
+    actual: usize
+  },
+}
+
+/// Display for Error
+impl core::fmt::Display for Error {
+  /// fmt
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    match self {
+    Error::Singular => write!(f, "singular matrix"),
+    Error::NotPositiveDefinite => write!(f, "not positive definite matrix"),
+    Error::NoRealLog => write!(f, "no real principal logarithm"),
+    Error::AtInfinity => write!(f, "point at infinity (w = 0)"),
+    Error::NotSquare(r, c) => write!(f, "not square matrix ({} x {})", r, c),
+    Error::DimensionMismatch{expected, actual} =>
+      write!(f, "dimension mismatch (expected {} actual {})", expected, actual)
+    }
+  }
+}
+
+/// std::error::Error for Error
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// Result
+pub type Result<T> = core::result::Result<T, Error>;
@@ -1,13 +1,29 @@
 #![doc(html_root_url = "https://docs.rs/quaternion-matrix/0.1.4")]
 //! quaternion matrix for Rust
 //!
+//! - no_std: default-features = false, features = ["libm"]
+//! - alloc: Vec based decompositions (lu qr eig svd expm chol) without std
+//!
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("either feature \"std\" or \"libm\" must be enabled");
+
+pub mod e;
 pub mod q;
 pub mod m;
 pub mod v;
+pub mod w;
+pub mod t;
 
 use num::Float;
 
+use crate::t::Tol;
+
 /// check equal with precision
 pub fn prec_eq_f<F: Float>(s: F, e: F, d: F) -> bool {
   (s - d).abs() < e
@@ -19,20 +35,51 @@ pub fn prec_eq<F: Float>(s: &[F], e: F, d: &[F]) -> bool {
   true
 }
 
+/// total order (NaN is greater than any number, for sort_by without panic)
+pub fn total_cmp_f<F: Float>(s: F, d: F) -> core::cmp::Ordering {
+  s.partial_cmp(&d).unwrap_or_else(|| s.is_nan().cmp(&d.is_nan()))
+}
+
+/// check equal with tolerance (Abs Rel Ulp Mixed)
+pub fn tol_eq_f<F: Float>(s: F, t: Tol<F>, d: F) -> bool {
+  t.check(s, d)
+}
+
+/// check equal with tolerance (Abs Rel Ulp Mixed, false when lengths differ)
+pub fn tol_eq<F: Float>(s: &[F], t: Tol<F>, d: &[F]) -> bool {
+  s.len() == d.len() && s.iter().zip(d.iter()).all(|(&p, &q)| t.check(p, q))
+}
+
 /// preq_eq macro
+/// - prec_eq!(a, t, b): any TolEq (t: Tol or abs prec)
+/// - prec_eq!(q, e, f, qe): Quaternion
 #[macro_export]
 macro_rules! prec_eq {
   ($qs: expr, $e: expr, $f: ident, $qe: expr) => {
-    $qs.prec_eq($e, &Quaternion::<$f>::new($qe))
-  }
+    $qs.prec_eq($e,
+      &<$crate::q::Quaternion<$f> as $crate::v::TVector<$f>>::new($qe))
+  };
+  ($a: expr, $t: expr, $b: expr) => {
+    $crate::t::TolEq::tol_eq(&$a, &$b, $crate::t::Tol::from($t))
+  };
 }
 
-/// assert_pe macro
+/// assert_pe macro (prints element wise difference on failure)
+/// - assert_pe!(a, t, b): any TolEq (t: Tol or abs prec)
+/// - assert_pe!(q, e, f, qe): Quaternion
 #[macro_export]
 macro_rules! assert_pe {
   ($qs: expr, $e: expr, $f: ident, $qe: expr) => {
-    assert!(prec_eq!($qs, $e, $f, $qe))
-  }
+    $crate::assert_pe!($qs, $e,
+      <$crate::q::Quaternion<$f> as $crate::v::TVector<$f>>::new($qe))
+  };
+  ($a: expr, $t: expr, $b: expr) => {{
+    let (a, b, t) = (&$a, &$b, $crate::t::Tol::from($t));
+    if !$crate::t::TolEq::tol_eq(a, b, t) {
+      panic!("assertion failed: {} == {} ({:?})\n  left: {:?}\n right: {:?}\n  diff: {}",
+        stringify!($a), stringify!($b), t, a, b, $crate::t::Diff(a, b));
+    }
+  }};
 }
 
 /// check_q macro
@@ -46,12 +93,22 @@ macro_rules! check_qaa {
 }
 
 /// test with [-- --nocapture] or [-- --show-output]
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
+#[allow(clippy::bool_assert_comparison)]
+#[allow(clippy::approx_constant)]
+#[allow(clippy::excessive_precision)]
+#[allow(clippy::useless_vec)]
 mod tests {
   // use super::*;
-  use crate::v::{TVector, v3::Vector3, v4::Vector4};
+  use crate::e::Error;
+  use crate::v::{TVector, vn::Vector, v3::{TV3, Vector3}, v4::{TV4, Vector4}};
   use crate::q::{TQuaternion, Quaternion};
-  use crate::m::{TMatrix, m3::Matrix3, m4::Matrix4};
+  use crate::m::{TMatrix, mn::Matrix, m3::{TM3, Matrix3}, m4::{TM4, Matrix4}};
+  use crate::w::{Vect, Mat, Mat4, Quat};
+  use crate::t::{Tol, TolEq, Diff};
+  use crate::m::frustum::{Frustum, Cull};
+  use crate::m::xform::{Xform, ColVec, RowVec};
+  use crate::m::{Conv, affine::{TAffine, Decomp, q_from_rot3, rot3}, cam::{TCam, Proj, Hand, Depth, project, unproject, pick_ray}};
 
   /// test Vector3
   #[test]
@@ -147,9 +204,9 @@ mod tests {
   fn test_quaternion_rot() {
     let pi32 = std::f32::consts::PI;
     let pi64 = std::f64::consts::PI;
-    let t32 = (0..=4).into_iter().map(|i|
+    let t32 = (0..=4).map(|i|
       i as f32 * pi32 / 2.0).collect::<Vec<_>>();
-    let t64 = (0..=4).into_iter().map(|i|
+    let t64 = (0..=4).map(|i|
       i as f64 * pi64 / 2.0).collect::<Vec<_>>();
     let ax32 = Vector3::<f32>::new(&vec![1.0, 0.0, 0.0]);
     let ay32 = Vector3::<f32>::new(&vec![0.0, 1.0, 0.0]);
@@ -386,4 +443,1026 @@ mod tests {
     assert!(v32.inv(1e-6).expect("det").prec_eq(1e-6, &u32));
     assert!(v64.inv(1e-6).expect("det").prec_eq(1e-6, &u64));
   }
+
+  /// test LU
+  #[test]
+  fn test_lu() {
+    let n = 6;
+    let t = (0..n).map(|j| (0..n).map(|i|
+      if i == j { 2.0 } else if i + 1 == j || j + 1 == i { -1.0 } else { 0.0 }
+    ).collect::<Vec<_>>()).collect::<Vec<Vec<f64>>>();
+    let lu = crate::m::lu::LU::new(&t).expect("square");
+    assert!(crate::prec_eq_f(lu.det(), 1e-10, 7.0)); // det = n + 1
+    assert!(crate::prec_eq_f(crate::m::det(&t).expect("square"), 1e-10, 7.0));
+    let (l, u) = (lu.l(), lu.u());
+    for j in 0..n {
+      let r = (0..n).map(|i|
+        (0..n).map(|k| l[j][k] * u[k][i]).sum::<f64>()
+      ).collect::<Vec<_>>();
+      assert!(crate::prec_eq(&r, 1e-10, &t[lu.piv[j]])); // P t = L U
+    }
+    let ti = crate::m::inv(&t, 1e-10).expect("det");
+    for (j, r) in t.iter().enumerate() {
+      for (i, c) in crate::m::transpose(&ti).expect("shape").iter().enumerate() {
+        let e = r.iter().zip(c.iter()).map(|(&a, &b)| a * b).sum::<f64>();
+        assert!(crate::prec_eq_f(e, 1e-10, if i == j { 1.0 } else { 0.0 }));
+      }
+    }
+    let b = vec![vec![1.0, 0.0], vec![0.0, 0.0], vec![0.0, 0.0],
+      vec![0.0, 0.0], vec![0.0, 0.0], vec![0.0, 1.0]];
+    let x = lu.solve_m(&b);
+    for j in 0..n {
+      assert!(crate::prec_eq(&x[j], 1e-10, &[ti[j][0], ti[j][n - 1]]));
+    }
+    let s = vec![vec![1.0, 2.0, 3.0], vec![2.0, 4.0, 6.0], vec![1.0, 0.0, 1.0]];
+    assert_eq!(crate::m::det(&s), Ok(0.0));
+    assert_eq!(crate::m::inv(&s, 1e-10), Err(Error::Singular));
+    let k = t.iter().map(|r| r.iter().map(|a| a * 1e-3).collect::<Vec<_>>())
+      .collect::<Vec<_>>(); // det = 7e-18 but pivots are not small
+    assert!(crate::m::inv(&k, 1e-10).is_ok());
+    assert_eq!(crate::m::lu::LU::new(&vec![vec![1.0, 2.0]]).err(),
+      Some(Error::NotSquare(1, 2)));
+    assert_eq!(crate::m::lu::LU::<f64>::new(&vec![]).err(),
+      Some(Error::NotSquare(0, 0)));
+  }
+
+  /// test solve
+  #[test]
+  fn test_solve() {
+    let u64 = Matrix3::<f64>::new(&vec![
+      vec![1.0, 2.0, 1.0],
+      vec![2.0, 1.0, 0.0],
+      vec![1.0, 1.0, 2.0]]);
+    let b64 = Vector3::<f64>::new(&vec![4.0, 3.0, 4.0]); // u64 dot [1, 1, 1]
+    let x64 = b64.solve_mv(&u64, 1e-10).expect("solve");
+    assert!(x64.prec_eq(1e-10, &[1.0, 1.0, 1.0]));
+    let i64 = Matrix3::<f64>::identity();
+    let v64 = i64.solve_m(&u64, 1e-10).expect("solve"); // u64 dot v64 = i64
+    assert!(v64.prec_eq(1e-10, &u64.inv(1e-10).expect("det")));
+    let u32 = Matrix4::<f32>::new(&vec![
+      vec![3.0, 1.0, 1.0, 2.0],
+      vec![5.0, 1.0, 3.0, 4.0],
+      vec![2.0, 0.0, 1.0, 0.0],
+      vec![1.0, 3.0, 2.0, 1.0]]);
+    let b32 = Vector4::<f32>::new(&vec![7.0, 13.0, 3.0, 7.0]);
+    let x32 = b32.solve_mv(&u32, 1e-6).expect("solve");
+    assert!(x32.prec_eq(1e-5, &[1.0, 1.0, 1.0, 1.0]));
+
+    let s = vec![vec![1.0, 2.0], vec![2.0, 4.0]];
+    assert_eq!(crate::m::solve(&s, &vec![1.0, 2.0], 1e-10),
+      Err(Error::Singular));
+    assert_eq!(crate::m::solve(&s, &vec![1.0, 2.0, 3.0], 1e-10),
+      Err(Error::DimensionMismatch{expected: 2, actual: 3}));
+    assert_eq!(crate::m::solve(&vec![vec![1.0, 2.0]], &vec![1.0], 1e-10),
+      Err(Error::NotSquare(1, 2)));
+    let bm = vec![vec![1.0, 2.0], vec![3.0, 4.0]];
+    let xm = crate::m::solve_m(&vec![vec![2.0, 0.0], vec![0.0, 4.0]], &bm, 1e-10)
+      .expect("solve");
+    assert_eq!(xm, vec![vec![0.5, 1.0], vec![0.75, 1.0]]);
+  }
+
+  /// test QR
+  #[test]
+  fn test_qr() {
+    let m = vec![
+      vec![1.0, 2.0, 3.0],
+      vec![4.0, 5.0, 6.0],
+      vec![7.0, 8.0, 10.0],
+      vec![1.0, 0.0, 1.0]];
+    for qr in [crate::m::qr::QR::new(&m), crate::m::qr::QR::new_pivot(&m)] {
+      let qr = qr.expect("shape");
+      let (q, r) = (qr.q(), qr.r.clone());
+      for j in 0..4 {
+        for i in 0..3 {
+          let e = (0..3).map(|k| q[j][k] * r[k][i]).sum::<f64>();
+          assert!(crate::prec_eq_f(e, 1e-10, m[j][qr.piv[i]])); // m P = Q R
+        }
+      }
+      let qtq = crate::m::transpose(&q).expect("shape");
+      for (j, a) in qtq.iter().enumerate() {
+        for (i, b) in qtq.iter().enumerate() {
+          let e = a.iter().zip(b.iter()).map(|(&x, &y)| x * y).sum::<f64>();
+          assert!(crate::prec_eq_f(e, 1e-10, if i == j { 1.0 } else { 0.0 }));
+        }
+      }
+      assert_eq!(qr.rank(1e-10), 3);
+    }
+    let d = vec![vec![1.0, 2.0, 3.0], vec![2.0, 4.0, 6.0], vec![1.0, 0.0, 1.0]];
+    assert_eq!(crate::m::rank(&d, 1e-10), Ok(2));
+
+    // fit plane z = a x + b y + c to noisy Vector3 points
+    let p = [
+      [0.0, 0.0, 1.01], [1.0, 0.0, 2.99], [0.0, 1.0, -1.98],
+      [1.0, 1.0, 0.02], [2.0, 1.0, 1.99], [1.0, 2.0, -3.01]];
+    let a = p.iter().map(|v: &Vector3<f64>|
+      vec![v[0], v[1], 1.0]).collect::<Vec<_>>();
+    let b = p.iter().map(|v| v[2]).collect::<Vec<_>>();
+    let x = crate::m::lstsq(&a, &b, 1e-10).expect("lstsq");
+    assert!(crate::prec_eq(&x, 0.05, &[2.0, -3.0, 1.0]));
+    // exact underdetermined (rank deficient) basic solution
+    let x = crate::m::lstsq(&d, &vec![6.0, 12.0, 2.0], 1e-10).expect("lstsq");
+    let r = d.iter().map(|r| r.iter().zip(x.iter()).map(|(a, b)|
+      a * b).sum::<f64>()).collect::<Vec<_>>();
+    assert!(crate::prec_eq(&r, 1e-10, &[6.0, 12.0, 2.0]));
+    assert_eq!(crate::m::lstsq(&d, &vec![1.0], 1e-10),
+      Err(Error::DimensionMismatch{expected: 3, actual: 1}));
+  }
+
+  /// test eigen_sym
+  #[test]
+  fn test_eigen_sym() {
+    let m64 = Matrix3::<f64>::new(&vec![ // repeated eigenvalue 3
+      vec![2.0, 1.0, 0.0],
+      vec![1.0, 2.0, 0.0],
+      vec![0.0, 0.0, 3.0]]);
+    let (w, v) = m64.eigen_sym();
+    assert!(crate::prec_eq(&w, 1e-10, &[3.0, 3.0, 1.0]));
+    assert!(v.transpose().dot_m(&v).prec_eq(1e-10, &Matrix3::identity()));
+    let d = Matrix3::<f64>::new(&vec![
+      vec![w[0], 0.0, 0.0], vec![0.0, w[1], 0.0], vec![0.0, 0.0, w[2]]]);
+    assert!(v.transpose().dot_m(&d.dot_m(&v)).prec_eq(1e-10, &m64));
+    let r = 0.5f64.sqrt();
+    assert!(v.colv3(2).prec_eq(1e-10, &[r, -r, 0.0]));
+
+    // average of quaternions = principal eigenvector of sum q q^T
+    let ax = Vector3::<f32>::new(&vec![0.0, 0.0, 1.0]);
+    let qs = [0.1f32, 0.2, 0.3].iter().map(|&a|
+      Quaternion::<f32>::from_axis_and_angle(&ax, a)).collect::<Vec<_>>();
+    let m32 = Matrix4::<f32>::new(&(0..4).map(|j|
+      (0..4).map(|i|
+        qs.iter().map(|q| q[j] * q[i]).sum::<f32>()).collect()).collect());
+    let (w, v) = m32.eigen_sym();
+    assert!(w[0] > 2.99 && w.windows(2).all(|p| p[0] >= p[1]));
+    let q = Quaternion::<f32>::from_axis_and_angle(&ax, 0.2);
+    assert!(v.colv4(0).prec_eq(1e-3, &q));
+    let n = vec![vec![f64::NAN, 0.0], vec![0.0, 1.0]];
+    assert!(crate::m::eigen_sym(&n).is_ok()); // NaN does not panic in sort
+    assert_eq!(crate::total_cmp_f(f64::NAN, 1.0), core::cmp::Ordering::Greater);
+  }
+
+  /// test svd
+  #[test]
+  fn test_svd() {
+    let m64 = Matrix3::<f64>::new(&vec![
+      vec![3.0, 1.0, 1.0],
+      vec![-1.0, 3.0, 1.0],
+      vec![0.0, 2.0, -2.0]]);
+    let (u, s, vt) = m64.svd();
+    assert!(s.windows(2).all(|p| p[0] >= p[1]));
+    assert!(u.transpose().dot_m(&u).prec_eq(1e-10, &Matrix3::identity()));
+    assert!(vt.dot_m(&vt.transpose()).prec_eq(1e-10, &Matrix3::identity()));
+    let d = Matrix3::<f64>::new(&vec![
+      vec![s[0], 0.0, 0.0], vec![0.0, s[1], 0.0], vec![0.0, 0.0, s[2]]]);
+    assert!(vt.dot_m(&d.dot_m(&u)).prec_eq(1e-10, &m64)); // u dot d dot vt
+
+    // reflection: proper rotations with negative last singular value
+    let r64 = Matrix3::<f64>::new(&vec![
+      vec![1.0, 0.0, 0.0], vec![0.0, 2.0, 0.0], vec![0.0, 0.0, -3.0]]);
+    let (u, s, vt) = r64.svd_rot();
+    assert!(crate::prec_eq_f(u.det(), 1e-10, 1.0));
+    assert!(crate::prec_eq_f(vt.det(), 1e-10, 1.0));
+    assert!(crate::prec_eq(&s, 1e-10, &[3.0, 2.0, -1.0]));
+
+    // rank deficient rectangular (wide and tall)
+    let r = vec![vec![1.0, 2.0, 3.0, 4.0], vec![2.0, 4.0, 6.0, 8.0f32]];
+    for m in [r.clone(), crate::m::transpose(&r).expect("shape"),
+      vec![vec![1.0, 2.0, 3.0, 4.0], vec![-2.0, -4.0, -6.0, -8.0]]] {
+      let d = crate::m::svd::SVD::new(&m).expect("shape");
+      assert!(crate::prec_eq(&d.s, 1e-4, &[150.0f32.sqrt(), 0.0]));
+      let c = d.compose();
+      for j in 0..m.len() { assert!(crate::prec_eq(&c[j], 1e-5, &m[j])); }
+      let k = d.s.len();
+      for j in 0..k {
+        for i in 0..k {
+          let e = (0..d.u.len()).map(|p|
+            d.u[p][j] * d.u[p][i]).sum::<f32>();
+          assert!(crate::prec_eq_f(e, 1e-6, if i == j { 1.0 } else { 0.0 }));
+        }
+      }
+      for v in d.vt.iter() { // largest component is positive (also when wide)
+        assert!(v.iter().fold(0.0f32, |p, &x| if x.abs() > p.abs() { x } else { p }) > 0.0);
+      }
+    }
+    let n = vec![vec![f64::NAN, 0.0], vec![0.0, 1.0]];
+    assert!(crate::m::svd(&n).is_ok()); // NaN does not panic in sort
+  }
+
+  /// test polar and orthonormalize
+  #[test]
+  fn test_polar() {
+    let ax = Vector3::<f64>::new(&vec![1.0, 2.0, 3.0]);
+    let q = Quaternion::<f64>::from_axis_and_angle(&ax, 0.7);
+    let r4 = q.to_m4_rot();
+    assert!(r4.is_rot(1e-10));
+    let mut d4 = r4;
+    for _ in 0..100 { d4 = d4.dot_m(&r4); } // drift
+    d4[0][1] += 1e-4;
+    d4[2][0] -= 2e-4;
+    assert!(!d4.is_rot(1e-6));
+    let (r, s) = d4.polar();
+    assert!(r.is_rot(1e-10));
+    assert!(s.prec_eq(1e-10, &s.transpose()));
+    assert!(s.dot_m(&r).prec_eq(1e-10, &d4)); // r dot s
+    assert!(d4.orthonormalize().expect("independent").is_rot(1e-10));
+    assert!(r.prec_eq(1e-3, &d4.orthonormalize().expect("independent")));
+
+    let m3 = Matrix3::<f32>::new(&vec![
+      vec![2.0, 0.0, 0.0],
+      vec![0.0, 0.0, -3.0],
+      vec![0.0, 1.0, 0.0]]);
+    let (r, s) = m3.polar();
+    assert!(r.prec_eq(1e-6, &Matrix3::<f32>::new(&vec![
+      vec![1.0, 0.0, 0.0], vec![0.0, 0.0, -1.0], vec![0.0, 1.0, 0.0]])));
+    assert!(s.prec_eq(1e-5, &Matrix3::<f32>::new(&vec![
+      vec![2.0, 0.0, 0.0], vec![0.0, 1.0, 0.0], vec![0.0, 0.0, 3.0]])));
+    let (o, d) = m3.rot_error();
+    assert!(o > 1.0 && crate::prec_eq_f(d, 1e-6, 5.0));
+    let f3 = Matrix3::<f32>::new(&vec![ // reflection
+      vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 0.0], vec![0.0, 0.0, -1.0]]);
+    assert!(!f3.is_rot(1e-6));
+    assert!(crate::prec_eq_f(f3.rot_error().0, 1e-6, 0.0));
+    let z3 = Matrix3::<f32>::new(&vec![ // zero column
+      vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 0.0], vec![0.0, 0.0, 0.0]]);
+    assert_eq!(z3.orthonormalize(), Err(Error::Singular));
+    let p = vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 9.0], vec![7.0, 8.0, 15.0]];
+    assert_eq!(crate::m::orthonormalize(&p), Err(Error::Singular)); // c2 = c0 + c1
+  }
+
+  /// test expm and logm
+  #[test]
+  fn test_expm_logm() {
+    // skew symmetric 3x3 (Rodrigues) = rotation
+    let w = Vector3::<f64>::new(&vec![0.3, -0.2, 0.6]);
+    let k = Matrix3::<f64>::new(&vec![
+      vec![0.0, -w[2], w[1]],
+      vec![w[2], 0.0, -w[0]],
+      vec![-w[1], w[0], 0.0]]);
+    let r = k.expm();
+    let q = Quaternion::<f64>::from_axis_and_angle(&w, w.dot(&w).sqrt());
+    let r4 = q.to_m4_rot();
+    assert!(r.prec_eq(1e-10, &[
+      [r4[0][0], r4[0][1], r4[0][2]],
+      [r4[1][0], r4[1][1], r4[1][2]],
+      [r4[2][0], r4[2][1], r4[2][2]]]));
+    assert!(r.logm().expect("logm").prec_eq(1e-10, &k));
+    let p = Matrix3::<f64>::new(&vec![ // rotation pi about z
+      vec![-1.0, 0.0, 0.0], vec![0.0, -1.0, 0.0], vec![0.0, 0.0, 1.0]]);
+    assert!(p.logm().expect("logm").expm().prec_eq(1e-10, &p));
+
+    // general (Pade) nilpotent and diagonal
+    let n = vec![vec![0.0, 1.0], vec![0.0, 0.0]];
+    assert_eq!(crate::m::expm(&n), Ok(vec![vec![1.0, 1.0], vec![0.0, 1.0]]));
+    let d = Matrix3::<f64>::new(&vec![
+      vec![1.0, 0.0, 0.0], vec![0.0, -2.0, 0.0], vec![0.0, 0.0, 5.0]]);
+    let e = [1.0f64.exp(), (-2.0f64).exp(), 5.0f64.exp()];
+    assert!(d.expm().prec_eq(1e-8, &[
+      [e[0], 0.0, 0.0], [0.0, e[1], 0.0], [0.0, 0.0, e[2]]]));
+
+    // affine Matrix4 interpolation: exp(t log(m))
+    let mut m = r4;
+    m[0][3] = 2.0;
+    m[1][3] = -1.0;
+    m[2][3] = 0.5;
+    let l = m.logm().expect("logm");
+    assert!(l.expm().prec_eq(1e-10, &m));
+    let h = Matrix4::<f64>::new(&l.to_vec().iter().map(|r|
+      r.iter().map(|a| a * 0.5).collect()).collect());
+    let h = h.expm();
+    assert!(h.dot_m(&h).prec_eq(1e-10, &m)); // half transform twice
+    let u32 = Matrix4::<f32>::new(&vec![
+      vec![3.0, 1.0, 1.0, 2.0],
+      vec![0.0, 2.0, 1.0, 0.0],
+      vec![0.0, 1.0, 3.0, 0.0],
+      vec![1.0, 0.0, 0.0, 4.0]]);
+    assert!(u32.logm().expect("logm").expm().prec_eq(1e-4, &u32));
+
+    let ni = vec![vec![-1.0, 0.0], vec![0.0, -1.0]]; // no real log
+    assert_eq!(crate::m::logm(&ni), Err(Error::NoRealLog));
+    let nd = vec![vec![-1.0, 0.0], vec![0.0, 1.0]];
+    assert_eq!(crate::m::logm(&nd), Err(Error::NoRealLog));
+  }
+
+  /// test Cholesky and LDL^T
+  #[test]
+  fn test_cholesky() {
+    let p64 = Matrix3::<f64>::new(&vec![
+      vec![4.0, 12.0, -16.0],
+      vec![12.0, 37.0, -43.0],
+      vec![-16.0, -43.0, 98.0]]);
+    let l = p64.cholesky().expect("spd");
+    assert!(l.prec_eq(1e-10, &[
+      [2.0, 0.0, 0.0], [6.0, 1.0, 0.0], [-8.0, 5.0, 3.0]]));
+    assert!(l.transpose().dot_m(&l).prec_eq(1e-10, &p64)); // l dot l^T
+    let b = vec![-20.0, -43.0, 192.0]; // p64 dot [1, 2, 3]
+    let x = crate::m::solve_spd(&p64.to_vec(), &b).expect("spd");
+    assert!(crate::prec_eq(&x, 1e-10, &[1.0, 2.0, 3.0]));
+    let ld = crate::m::log_det_spd(&p64.to_vec()).expect("spd");
+    assert!(crate::prec_eq_f(ld, 1e-10, 36.0f64.ln())); // det = (2 1 3)^2
+    let c = crate::m::chol::Cholesky::new(&p64.to_vec()).expect("spd");
+    assert!(Matrix3::<f64>::new(&c.inv()).prec_eq(1e-8, &p64.inv(1e-10).expect("det")));
+
+    let d = crate::m::chol::LDLT::new(&p64.to_vec()).expect("ldlt");
+    assert!(d.is_pd());
+    assert!(crate::prec_eq(&d.d, 1e-10, &[4.0, 1.0, 9.0]));
+    assert!(crate::prec_eq(&d.solve(&b), 1e-10, &[1.0, 2.0, 3.0]));
+    assert!(crate::prec_eq_f(d.log_det(), 1e-10, 36.0f64.ln()));
+
+    let n32 = Matrix3::<f32>::new(&vec![ // indefinite
+      vec![1.0, 2.0, 0.0],
+      vec![2.0, 1.0, 0.0],
+      vec![0.0, 0.0, 1.0]]);
+    assert_eq!(n32.cholesky(), Err(Error::NotPositiveDefinite));
+    let s32 = n32.nearest_spd();
+    assert!(s32.prec_eq(1e-6, &s32.transpose()));
+    assert!(s32.cholesky().is_ok());
+    assert!(s32.prec_eq(1e-5, &[[1.5, 1.5, 0.0], [1.5, 1.5, 0.0], [0.0, 0.0, 1.0]]));
+    assert_eq!(crate::m::solve_spd(&p64.to_vec(), &vec![1.0]),
+      Err(Error::DimensionMismatch{expected: 3, actual: 1}));
+    assert_eq!(crate::m::cholesky(&vec![vec![1.0, 0.0]]).err(),
+      Some(Error::NotSquare(1, 2)));
+
+    let mut a64 = p64.to_vec(); // asymmetric (upper triangle differs)
+    a64[0][2] = -15.0;
+    assert_eq!(crate::m::cholesky(&a64), Err(Error::NotPositiveDefinite));
+    assert_eq!(crate::m::solve_spd(&a64, &b), Err(Error::NotPositiveDefinite));
+    assert_eq!(crate::m::log_det_spd(&a64), Err(Error::NotPositiveDefinite));
+    a64[0][2] = -16.0 * (1.0 + f64::EPSILON); // within rounding
+    assert!(crate::m::cholesky(&a64).is_ok());
+
+    let v = [0.3f32, -1.7, 2.9, 0.01]; // rank 1 v v^T (near singular)
+    let r32 = Matrix4::<f32>::new(&v.iter().map(|&p|
+      v.iter().map(|&q| p * q).collect()).collect::<Vec<_>>());
+    assert_eq!(r32.cholesky(), Err(Error::NotPositiveDefinite));
+    assert!(r32.nearest_spd().cholesky().is_ok());
+    assert!(r32.nearest_spd().prec_eq(1e-5, &r32));
+    let t64 = vec![ // tiny negative eigenvalue
+      vec![1.0, 1.0, 0.0],
+      vec![1.0, 1.0 - 1e-15, 0.0],
+      vec![0.0, 0.0, 1e-20]];
+    let s64 = crate::m::nearest_spd(&t64).expect("square");
+    assert!(crate::m::cholesky(&s64).is_ok());
+  }
+
+  /// test pinv rank null_space and cond
+  #[test]
+  fn test_pinv() {
+    let u64 = Matrix3::<f64>::new(&vec![
+      vec![1.0, 2.0, 1.0],
+      vec![2.0, 1.0, 0.0],
+      vec![1.0, 1.0, 2.0]]);
+    assert!(u64.pinv(1e-12).prec_eq(1e-10, &u64.inv(1e-10).expect("det")));
+    assert_eq!(u64.rank(1e-12), 3);
+    assert!(u64.cond() > 1.0);
+    assert!(crate::prec_eq_f(Matrix3::<f64>::identity().cond(), 1e-10, 1.0));
+
+    // flattened scale (projection onto xy plane)
+    let s32 = Matrix4::<f32>::new(&vec![
+      vec![2.0, 0.0, 0.0, 1.0],
+      vec![0.0, 3.0, 0.0, 2.0],
+      vec![0.0, 0.0, 1e-9, 0.0],
+      vec![0.0, 0.0, 0.0, 1.0]]);
+    assert!(s32.inv(1e-6).is_none()); // absolute prec
+    assert_eq!(s32.rank(1e-6), 3);
+    assert!(s32.cond() > 1e6);
+    let p = s32.pinv(1e-6);
+    assert!(p.prec_eq(1e-6, &[
+      [0.5, 0.0, 0.0, -0.5],
+      [0.0, 1.0 / 3.0, 0.0, -2.0 / 3.0],
+      [0.0, 0.0, 0.0, 0.0],
+      [0.0, 0.0, 0.0, 1.0]]));
+    assert!(p.dot_m(&s32.dot_m(&p)).prec_eq(1e-6, &p)); // p dot s dot p = p
+
+    let r = vec![vec![1.0, 2.0, 3.0], vec![2.0, 4.0, 6.0]]; // 2 x 3 rank 1
+    assert_eq!(crate::m::rank(&r, 1e-12), Ok(1));
+    let ns = crate::m::null_space(&r, 1e-12).expect("shape");
+    assert_eq!(ns.len(), 2);
+    for v in ns.iter() {
+      assert!(crate::prec_eq_f(v.iter().map(|a| a * a).sum::<f64>(), 1e-10, 1.0));
+      for w in r.iter() {
+        let d = w.iter().zip(v.iter()).map(|(a, b)| a * b).sum::<f64>();
+        assert!(crate::prec_eq_f(d, 1e-10, 0.0));
+      }
+    }
+    let p = crate::m::pinv(&r, 1e-12).expect("shape"); // 3 x 2
+    let rp = crate::m::mul(&r, &p).expect("shape");
+    assert!(crate::prec_eq(&rp[0], 1e-10, &[0.2, 0.4]));
+    assert!(crate::prec_eq(&rp[1], 1e-10, &[0.4, 0.8]));
+    assert!(crate::m::cond(&r).expect("shape").is_infinite());
+  }
+
+  /// test const generic Vector and Matrix
+  #[test]
+  fn test_generic() {
+    let v6 = Vector::<f64, 6>::new(&vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    assert_eq!(v6.dot(&v6), 91.0);
+    let i6 = Matrix::<f64, 6, 6>::identity();
+    assert_eq!(v6.dot_mv(&i6), v6);
+    let t6 = Matrix::<f64, 6, 6>::new(&(0..6).map(|j|
+      (0..6).map(|i|
+        if i == j { 2.0 } else if i + 1 == j || j + 1 == i { -1.0 } else { 0.0 }
+      ).collect()).collect());
+    assert!(crate::prec_eq_f(t6.det(), 1e-10, 7.0));
+    assert!(t6.inv(1e-10).expect("det").dot_m(&t6).prec_eq(1e-10, &i6));
+
+    let m2 = Matrix::<f32, 2, 2>::new(&vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+    assert_eq!(m2.det(), -2.0);
+    assert_eq!(m2.transpose(), [[1.0, 3.0], [2.0, 4.0]]);
+    assert_eq!(m2.dot_m(&m2), [[7.0, 10.0], [15.0, 22.0]]);
+    assert_eq!(Matrix::<f32, 2, 2>::identity(), [[1.0, 0.0], [0.0, 1.0]]);
+
+    let a: Matrix<f64, 2, 3> = [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]];
+    let b: Matrix<f64, 3, 4> = crate::m::mn::from_vec(&vec![
+      vec![1.0, 0.0, 0.0, 1.0],
+      vec![0.0, 1.0, 0.0, 1.0],
+      vec![0.0, 0.0, 1.0, 1.0]]).expect("3 x 4");
+    assert_eq!(crate::m::mn::mul(&a, &b),
+      [[1.0, 2.0, 3.0, 6.0], [4.0, 5.0, 6.0, 15.0]]);
+    assert_eq!(crate::m::mn::mul_mv(&a, &[1.0, 1.0, 1.0]), [6.0, 15.0]);
+    assert_eq!(crate::m::mn::transposed(&a),
+      [[1.0, 4.0], [2.0, 5.0], [3.0, 6.0]]);
+    assert_eq!(crate::m::mn::to_vec(&a),
+      vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+    let m3: Matrix3<f64> = [[1.0, 2.0, 1.0], [2.0, 1.0, 0.0], [1.0, 1.0, 2.0]];
+    assert_eq!(m3.mev3()[1], [2.0, 1.0, 0.0]);
+    assert_eq!(m3.rowv3(2), [1.0, 1.0, 2.0]);
+    assert_eq!(m3.colv3(0), [1.0, 2.0, 1.0]);
+  }
+
+  /// test closed form det and inv on the stack
+  #[test]
+  fn test_closed_form() {
+    let u64 = Matrix4::<f64>::new(&vec![
+      vec![3.0, 1.0, 1.0, 2.0],
+      vec![5.0, 1.0, 3.0, 4.0],
+      vec![2.0, 0.0, 1.0, 0.0],
+      vec![1.0, 3.0, 2.0, 1.0]]);
+    assert!(crate::prec_eq_f(u64.det(), 1e-10, crate::m::det(&u64.to_vec()).expect("square")));
+    let v64 = Matrix4::<f64>::new(&crate::m::inv(&u64.to_vec(), 1e-10).expect("det"));
+    assert!(u64.inv(1e-10).expect("det").prec_eq(1e-10, &v64));
+    let m3: Matrix3<f32> = [[2.0, -1.0, 0.0], [-1.0, 2.0, -1.0], [0.0, -1.0, 2.0]];
+    assert_eq!(m3.det(), 4.0);
+    assert!(m3.inv(1e-6).expect("det").dot_m(&m3).prec_eq(1e-6, &Matrix3::identity()));
+    assert_eq!(m3.transpose(), m3);
+    let s4: Matrix4<f32> = [[1.0, 2.0, 3.0, 4.0], [2.0, 4.0, 6.0, 8.0],
+      [0.0, 1.0, 0.0, 1.0], [1.0, 0.0, 1.0, 0.0]];
+    assert_eq!(s4.det(), 0.0);
+    assert!(s4.inv(1e-6).is_none());
+    let m1: Matrix<f64, 1, 1> = [[4.0]];
+    assert_eq!(m1.inv(1e-10), Some([[0.25]]));
+    let v = Vector3::<f64>::new(&[1.0, 2.0, 3.0]);
+    assert_eq!(v.cross(&[0.0, 0.0, 1.0]), [2.0, -1.0, 0.0]);
+    assert_eq!(Quaternion::<f64>::identity(), [1.0, 0.0, 0.0, 0.0]);
+  }
+
+  /// test_stack
+  #[test]
+  fn test_stack() {
+    let m: Matrix<f64, 5, 5> = [
+      [4.0, 1.0, 0.0, 2.0, 1.0],
+      [1.0, 5.0, 1.0, 0.0, 0.0],
+      [0.0, 2.0, 6.0, 1.0, 3.0],
+      [2.0, 0.0, 1.0, 3.0, 0.0],
+      [0.0, 1.0, 0.0, 1.0, 7.0]];
+    assert!(crate::prec_eq_f(m.det(), 1e-9, crate::m::det(&m.to_vec()).expect("square")));
+    let i = m.inv(1e-10).expect("det");
+    assert!(i.dot_m(&m).prec_eq(1e-12, &Matrix::<f64, 5, 5>::identity()));
+    let mut s = m;
+    s[4] = [8.0, 2.0, 0.0, 4.0, 2.0]; // 2 x row 0
+    assert!(s.inv(1e-10).is_none());
+  }
+
+  /// test_ops
+  #[test]
+  fn test_ops() {
+    let a = Mat::<f64, 2, 3>([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    let b = Mat::<f64, 3, 2>([[1.0, 0.0], [0.0, 1.0], [1.0, 1.0]]);
+    assert_eq!(a * b, Mat([[4.0, 5.0], [10.0, 11.0]]));
+    let m: Matrix4<f64> = [[2.0, 0.0, 0.0, 1.0], [0.0, 3.0, 0.0, 2.0],
+      [0.0, 0.0, 4.0, 3.0], [0.0, 0.0, 0.0, 1.0]];
+    let v: Vector4<f64> = [1.0, 1.0, 1.0, 1.0];
+    assert_eq!((Mat(m) * Vect(v)).0, v.dot_mv(&m)); // m * v is m dot v
+    let n = Mat4::<f64>::identity() * 2.0;
+    assert_eq!((Mat(m) * n).0, n.0.dot_m(&m)); // a * b is a dot b
+    let mut c = Mat(m);
+    c *= n;
+    c[(0, 0)] -= 4.0;
+    assert_eq!(c[0], [0.0, 0.0, 0.0, 2.0]);
+    let mut w = Vect([1.0f32, 2.0, 3.0]) + Vect([1.0, 1.0, 1.0]) * 2.0;
+    w -= -Vect([1.0, 0.0, 0.0]);
+    w[2] /= 5.0;
+    assert_eq!(w, Vect([4.0, 4.0, 1.0]));
+    assert_eq!(w.cross(&Vect([0.0, 0.0, 1.0])).dot(&w), 0.0);
+    let arr: [f32; 3] = w.into();
+    assert_eq!(Vect::from(arr), w);
+    let (i, j) = (Quat([0.0, 1.0, 0.0, 0.0]), Quat([0.0, 0.0, 1.0, 0.0]));
+    assert_eq!(i * j, Quat([0.0, 0.0, 0.0, 1.0])); // i j = k
+    assert_eq!(j * i, Quat([0.0, 0.0, 0.0, -1.0]));
+    let q = Quat(Quaternion::<f64>::from_axis_and_angle(&[0.0, 0.0, 1.0],
+      std::f64::consts::PI / 2.0));
+    let r = q.rotate(&Vect([1.0, 0.0, 0.0]));
+    assert!(r.prec_eq(1e-12, &[0.0, 1.0, 0.0])); // through Deref
+    let mut p = q;
+    p *= q.conjugate();
+    assert!(p.prec_eq(1e-12, &Quaternion::<f64>::identity()));
+  }
+
+  /// test_errors
+  #[test]
+  fn test_errors() {
+    let r = vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]; // 2 x 3
+    assert_eq!(crate::m::det(&r), Err(Error::NotSquare(2, 3)));
+    assert_eq!(crate::m::inv(&r, 1e-10), Err(Error::NotSquare(2, 3)));
+    assert_eq!(crate::m::cofactor(&r, 0, 0), Err(Error::NotSquare(2, 3)));
+    assert_eq!(crate::m::eigen_sym(&r), Err(Error::NotSquare(2, 3)));
+    assert_eq!(crate::m::expm(&r), Err(Error::NotSquare(2, 3)));
+    assert_eq!(crate::m::logm(&r), Err(Error::NotSquare(2, 3)));
+    assert!(crate::m::polar(&r).is_err());
+    assert!(crate::m::rot_error(&r).is_err());
+    assert!(crate::m::nearest_spd(&r).is_err());
+    let mismatch = |expected, actual| Error::DimensionMismatch{expected, actual};
+    let g = vec![vec![1.0, 2.0], vec![3.0]]; // ragged
+    assert_eq!(crate::m::transpose(&g), Err(mismatch(2, 1)));
+    assert_eq!(crate::m::mul(&g, &r), Err(mismatch(2, 1)));
+    assert_eq!(crate::m::mul(&r, &r), Err(mismatch(3, 2)));
+    assert_eq!(crate::m::svd(&g).err(), Some(mismatch(2, 1)));
+    assert_eq!(crate::m::rank(&g, 1e-10), Err(mismatch(2, 1)));
+    assert_eq!(crate::m::pinv(&g, 1e-10), Err(mismatch(2, 1)));
+    assert_eq!(crate::m::null_space(&g, 1e-10), Err(mismatch(2, 1)));
+    assert_eq!(crate::m::cond(&g), Err(mismatch(2, 1)));
+    assert_eq!(crate::m::lstsq(&g, &[1.0, 2.0], 1e-10), Err(mismatch(2, 1)));
+    assert_eq!(crate::m::eigen_sym(&g), Err(mismatch(2, 1)));
+    assert_eq!(crate::m::expm(&g), Err(mismatch(2, 1)));
+    let z: Vec<Vec<f64>> = vec![]; // empty
+    assert_eq!(crate::m::svd(&z).err(), Some(mismatch(1, 0)));
+    assert_eq!(crate::m::transpose(&vec![vec![0.0f64; 0]]), Err(mismatch(1, 0)));
+    assert_eq!(crate::m::det(&z), Err(Error::NotSquare(0, 0)));
+    assert_eq!(crate::m::polar(&z), Err(Error::NotSquare(0, 0)));
+    assert_eq!(crate::m::cofactor(&vec![vec![2.0, 1.0], vec![1.0, 3.0]], 0, 1),
+      Ok(-1.0));
+    let m: crate::e::Result<Matrix<f64, 3, 3>> = crate::m::mn::from_vec(&r);
+    assert_eq!(m, Err(Error::DimensionMismatch{expected: 3, actual: 2}));
+    let m: crate::e::Result<Matrix<f64, 2, 2>> = crate::m::mn::from_vec(&r);
+    assert_eq!(m, Err(Error::DimensionMismatch{expected: 2, actual: 3}));
+    // size specific methods are in TM3 TM4 TV3 (Matrix3 vs Matrix4 does not compile)
+    let m3 = Matrix3::<f64>::colmajor3([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0],
+      [7.0, 8.0, 9.0]]);
+    assert_eq!(m3.rowv3(0), [1.0, 4.0, 7.0]);
+    assert_eq!(m3.colv3(2), [7.0, 8.0, 9.0]);
+    let m4 = Matrix4::<f32>::rowmajor4([[1.0, 2.0, 3.0, 4.0]; 4]);
+    assert_eq!(m4.colv4(3), [4.0; 4]);
+    assert_eq!(m4.mev4()[2], m4.rowv4(2));
+    let r3: Matrix3<f64> = [[0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]];
+    assert!(r3.is_rot(1e-10)); // rot_error works on the stack
+    assert!(!m3.is_rot(1e-10));
+  }
+
+  /// test_try_from
+  #[test]
+  fn test_try_from() {
+    let mismatch = |expected, actual| Error::DimensionMismatch{expected, actual};
+    assert_eq!(Vector3::<f64>::try_from_slice(&[1.0, 2.0, 3.0]), Ok([1.0, 2.0, 3.0]));
+    assert_eq!(Vector3::<f64>::try_from_slice(&[1.0, 2.0]), Err(mismatch(3, 2)));
+    assert_eq!(Vector4::<f32>::try_from_iter((0..6).map(|i| i as f32)),
+      Err(mismatch(4, 6)));
+    assert_eq!(Quaternion::<f64>::try_from_iter([1.0, 0.0, 0.0, 0.0]),
+      Ok(Quaternion::<f64>::identity()));
+    assert_eq!(Matrix3::<f64>::try_new(&vec![vec![1.0, 0.0, 0.0]; 2]),
+      Err(mismatch(3, 2)));
+    assert_eq!(Matrix3::<f64>::try_new(&vec![vec![1.0, 0.0]; 3]),
+      Err(mismatch(3, 2)));
+    assert_eq!(Matrix3::<f64>::try_from_slice(&[0.0; 9]), Ok([[0.0; 3]; 3]));
+    assert_eq!(Matrix4::<f64>::try_from_slice(&[0.0; 9]), Err(mismatch(16, 9)));
+    let m = Matrix::<f64, 2, 2>::try_from_iter((1..5).map(|i| i as f64));
+    assert_eq!(m, Ok([[1.0, 2.0], [3.0, 4.0]]));
+    let v: Result<Vect<f64, 3>, Error> = vec![1.0, 2.0].try_into();
+    assert_eq!(v, Err(mismatch(3, 2)));
+    let v = Vect::<f32, 2>::try_from(&[1.0f32, 2.0][..]).expect("2");
+    assert_eq!(v, Vect([1.0, 2.0]));
+    assert_eq!(Quat::<f64>::try_from(vec![1.0, 0.0, 0.0]), Err(mismatch(4, 3)));
+    assert_eq!(Quat::<f64>::try_from_iter([1.0, 0.0, 0.0, 0.0]), Ok(Quat::identity()));
+    let a = Mat::<f64, 2, 3>::try_from(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0][..]);
+    assert_eq!(a, Ok(Mat([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]])));
+    let b = Mat::<f64, 2, 2>::try_from(vec![vec![1.0, 2.0], vec![3.0]]);
+    assert_eq!(b, Err(mismatch(2, 1)));
+    assert_eq!(Mat::<f32, 2, 2>::try_from_iter([1.0; 5]), Err(mismatch(4, 5)));
+  }
+
+  /// test_tol
+  #[test]
+  fn test_tol() {
+    let (a, b) = (1e9f64, 1e9f64 + 0.5); // large translation
+    assert!(!crate::tol_eq_f(a, Tol::Abs(1e-6), b));
+    assert!(crate::tol_eq_f(a, Tol::Rel(1e-9), b));
+    assert!(!crate::tol_eq_f(1e-12, Tol::Rel(1e-6), 2e-12)); // tiny values
+    assert!(crate::tol_eq_f(1e-12, Tol::Mixed(1e-10, 1e-6), 2e-12));
+    assert!(crate::tol_eq_f(a, Tol::Mixed(1e-10, 1e-9), b));
+    let x = 1.0f32;
+    let y = x + f32::EPSILON * 2.0;
+    assert!(crate::tol_eq_f(x, Tol::Ulp(2), y));
+    assert!(!crate::tol_eq_f(x, Tol::Ulp(1), y));
+    assert!(crate::tol_eq_f(0.0f64, Tol::Ulp(1), 5e-324)); // smallest subnormal
+    assert!(!crate::tol_eq_f(f64::NAN, Tol::Rel(1.0), f64::NAN));
+    assert!(crate::tol_eq_f(f64::INFINITY, Tol::Ulp(0), f64::INFINITY));
+    assert!(!crate::tol_eq(&[1.0, 2.0], Tol::Abs(1.0), &[1.0]));
+    let m: Matrix4<f64> = [[1e6, 0.0, 0.0, 1e9], [0.0, 1.0, 0.0, 0.0],
+      [0.0, 0.0, 1.0, 0.0], [0.0, 0.0, 0.0, 1.0]];
+    let mut n = m;
+    n[0][3] += 1e-4;
+    assert!(!m.prec_eq(1e-6, &n));
+    assert!(m.prec_eq_tol(Tol::Mixed(1e-12, 1e-12), &n));
+    assert!(m[0].prec_eq_tol(Tol::Rel(1e-12), &n[0]));
+    assert!(prec_eq!(m, Tol::Rel(1e-12), n));
+    assert!(prec_eq!(m, 1e-3, n)); // abs prec
+    assert!(!prec_eq!(Vect([1.0f32, 2.0]), 1e-6, Vect([1.0, 2.1])));
+    assert_pe!(Quat([1.0, 0.0, 0.0, 0.0]), Tol::Ulp(4),
+      Quat::<f64>::identity() * Quat::identity());
+    assert_pe!(Mat([[1.0f32, 2.0]]), 1e-6, Mat([[1.0, 2.0]]));
+    assert_pe!(vec![1.0, 2.0], Tol::Abs(1e-6), vec![1.0, 2.0]);
+    assert_pe!(Quaternion::<f64>::identity(), 1e-6, f64, &[1.0, 0.0, 0.0, 0.0]);
+    assert!([[1.0f64, 2.0]].tol_eq(&[[1.0, 2.0]], Tol::Abs(1e-6)));
+    assert_eq!(format!("{}", Diff(&[[1.0f64, 2.0]], &[[1.0, 1.5]])),
+      "[[0e0, 5e-1]]");
+    let r = std::panic::catch_unwind(|| assert_pe!([1.0f64, 3.0], 1e-6, [1.0, 2.0]));
+    let e = r.expect_err("not equal");
+    let msg = e.downcast_ref::<String>().expect("message");
+    assert!(msg.contains("diff: [0e0, 1e0]"));
+  }
+
+  /// test_affine
+  #[test]
+  fn test_affine() {
+    let h = std::f64::consts::FRAC_PI_2;
+    let p: Vector4<f64> = [1.0, 0.0, 0.0, 1.0];
+    let row = |m: &Matrix4<f64>| crate::m::mn::mul(&[p], m)[0]; // p dot m
+    let t = Matrix4::<f64>::translate(&[1.0, 2.0, 3.0], Conv::Col);
+    assert_eq!(p.dot_mv(&t), [2.0, 2.0, 3.0, 1.0]);
+    assert_eq!(t.rowv4(3), [0.0, 0.0, 0.0, 1.0]);
+    let t = Matrix4::<f64>::translate(&[1.0, 2.0, 3.0], Conv::Row);
+    assert_eq!(row(&t), [2.0, 2.0, 3.0, 1.0]);
+    assert_eq!(t.rowv4(3), [1.0, 2.0, 3.0, 1.0]);
+    assert_eq!(p.dot_mv(&Matrix4::scale(&[2.0, 3.0, 4.0])), [2.0, 0.0, 0.0, 1.0]);
+    assert_eq!(Matrix4::scale_uniform(2.0), Matrix4::scale(&[2.0; 3]));
+    for c in [Conv::Col, Conv::Row] {
+      let f = |m: &Matrix4<f64>| if c == Conv::Col { p.dot_mv(m) } else { row(m) };
+      assert!(f(&Matrix4::rot_z(h, c)).prec_eq(1e-12, &[0.0, 1.0, 0.0, 1.0]));
+      assert!(f(&Matrix4::rot_y(h, c)).prec_eq(1e-12, &[0.0, 0.0, -1.0, 1.0]));
+      assert!(f(&Matrix4::rot_x(h, c)).prec_eq(1e-12, &p)); // on the axis
+      let a = [1.0, 2.0, 2.0];
+      let q = Quaternion::<f64>::from_axis_and_angle(&a, 0.7);
+      let r = Matrix4::rot_axis(&a, 0.7, c);
+      assert!(r.prec_eq(1e-12, &Matrix4::rot_q(&q, c)));
+      assert!(Matrix4::rot_axis(&[0.0, 0.0, 2.0], 0.7, c).prec_eq(1e-12,
+        &Matrix4::rot_z(0.7, c)));
+      let m = Matrix4::trs(&[1.0, 0.0, 0.0],
+        &Quaternion::from_axis_and_angle(&[0.0, 0.0, 1.0], h), &[2.0; 3], c);
+      assert!(f(&m).prec_eq(1e-12, &[1.0, 2.0, 0.0, 1.0])); // scale rot trans
+      let s = Matrix4::shear(0.5, 0.0, 0.0, 0.0, 0.0, 0.0, c);
+      assert_eq!(if c == Conv::Col { [0.0, 1.0, 0.0, 1.0].dot_mv(&s) }
+        else { crate::m::mn::mul(&[[0.0, 1.0, 0.0, 1.0]], &s)[0] },
+        [0.5, 1.0, 0.0, 1.0]);
+    }
+    let m = Matrix4::<f32>::trs(&[1.0, 2.0, 3.0],
+      &Quaternion::from_axis_and_angle(&[1.0, 1.0, 0.0], 0.3), &[1.0, 2.0, 3.0],
+      Conv::Row);
+    assert_eq!(m, Matrix4::trs(&[1.0, 2.0, 3.0],
+      &Quaternion::from_axis_and_angle(&[1.0, 1.0, 0.0], 0.3), &[1.0, 2.0, 3.0],
+      Conv::Col).transpose()); // Row is transpose of Col
+  }
+
+  /// test_cam
+  #[test]
+  fn test_cam() {
+    // clip then divide by w (row vector for Conv::Row)
+    let ndc = |m: &Matrix4<f64>, p: Proj, v: [f64; 3]| {
+      let v = [v[0], v[1], v[2], 1.0];
+      let c = match p.conv {
+      Conv::Col => v.dot_mv(m),
+      Conv::Row => crate::m::mn::mul(&[v], m)[0]
+      };
+      [c[0] / c[3], c[1] / c[3], c[2] / c[3]]
+    };
+    let rh_zo = Proj{depth: Depth::ZeroOne, ..Proj::GL};
+    let lh_no = Proj{hand: Hand::Left, depth: Depth::NegOneOne, conv: Conv::Row};
+    for (p, z, lo) in [(Proj::GL, -1.0, -1.0), (Proj::DX, 1.0, 0.0),
+      (Proj::VK, -1.0, 0.0), (rh_zo, -1.0, 0.0), (lh_no, 1.0, -1.0)] {
+      let m = Matrix4::<f64>::perspective(std::f64::consts::FRAC_PI_2, 2.0,
+        0.5, 100.0, p);
+      assert!(ndc(&m, p, [0.0, 0.0, z * 0.5]).prec_eq(1e-12, &[0.0, 0.0, lo]));
+      assert!(ndc(&m, p, [0.0, 0.0, z * 100.0]).prec_eq(1e-12, &[0.0, 0.0, 1.0]));
+      assert!(ndc(&m, p, [1.0, 1.0, z]).prec_eq(1e-12,
+        &[0.5, 1.0, ndc(&m, p, [0.0, 0.0, z])[2]])); // 90 degree, aspect 2
+      let f = Matrix4::<f64>::frustum(-1.0, 3.0, -2.0, 2.0, 1.0, 10.0, p);
+      assert!(ndc(&f, p, [-1.0, -2.0, z]).prec_eq(1e-12, &[-1.0, -1.0, lo]));
+      assert!(ndc(&f, p, [30.0, 20.0, z * 10.0]).prec_eq(1e-12, &[1.0, 1.0, 1.0]));
+      let s = Matrix4::<f64>::frustum(-1.0, 1.0, -1.0, 1.0, 1.0, 10.0, p);
+      let q = Matrix4::<f64>::perspective(std::f64::consts::FRAC_PI_2, 1.0,
+        1.0, 10.0, p);
+      assert!(s.prec_eq(1e-12, &q));
+      let o = Matrix4::<f64>::ortho(-2.0, 2.0, -1.0, 1.0, 1.0, 5.0, p);
+      assert!(ndc(&o, p, [-2.0, 1.0, z]).prec_eq(1e-12, &[-1.0, 1.0, lo]));
+      assert!(ndc(&o, p, [2.0, -1.0, z * 5.0]).prec_eq(1e-12, &[1.0, -1.0, 1.0]));
+      let i = Matrix4::<f64>::perspective_inf(1.0, 1.0, 0.5, p);
+      assert!(ndc(&i, p, [0.0, 0.0, z * 0.5]).prec_eq(1e-12, &[0.0, 0.0, lo]));
+      assert!(ndc(&i, p, [0.0, 0.0, z * 1e9])[2] < 1.0);
+      assert!(crate::prec_eq_f(ndc(&i, p, [0.0, 0.0, z * 1e9])[2], 1e-6, 1.0));
+      let r = Matrix4::<f64>::perspective_rev(1.0, 1.0, 0.5, 100.0, p);
+      assert!(crate::prec_eq_f(ndc(&r, p, [0.0, 0.0, z * 0.5])[2], 1e-12, 1.0));
+      assert!(crate::prec_eq_f(ndc(&r, p, [0.0, 0.0, z * 100.0])[2], 1e-12, lo));
+      let v = Matrix4::<f64>::look_at(&[1.0, 2.0, 3.0], &[1.0, 2.0, 0.0],
+        &[0.0, 1.0, 0.0], p);
+      assert!(ndc(&v, p, [1.0, 2.0, 3.0]).prec_eq(1e-12, &[0.0, 0.0, 0.0]));
+      assert!(ndc(&v, p, [1.0, 2.0, 0.0]).prec_eq(1e-12, &[0.0, 0.0, z * 3.0]));
+      assert!(ndc(&v, p, [1.0, 3.0, 3.0]).prec_eq(1e-12, &[0.0, 1.0, 0.0]));
+      assert!(ndc(&v, p, [2.0, 2.0, 3.0]).prec_eq(1e-12, &[-z, 0.0, 0.0]));
+      let t = Matrix4::<f64>::look_at(&[0.0, 5.0, 0.0], &[0.0, 0.0, 0.0],
+        &[0.0, 1.0, 0.0], p); // up is parallel to the view direction
+      assert!(t.iter().flatten().all(|x| x.is_finite()));
+      assert!(ndc(&t, p, [0.0, 0.0, 0.0]).prec_eq(1e-12, &[0.0, 0.0, z * 5.0]));
+      let (a, b) = (ndc(&t, p, [1.0, 5.0, 0.0]), ndc(&t, p, [0.0, 5.0, 1.0]));
+      assert!(crate::prec_eq_f(a.dot(&a), 1e-12, 1.0) && a[2].abs() < 1e-12);
+      assert!(crate::prec_eq_f(b.dot(&b), 1e-12, 1.0) && a.dot(&b).abs() < 1e-12);
+    }
+    // D3DXMatrixPerspectiveFovLH
+    let d = Matrix4::<f32>::perspective(1.0, 1.5, 1.0, 9.0, Proj::DX);
+    let y = 1.0 / 0.5f32.tan();
+    assert!(d.prec_eq(1e-6, &[[y / 1.5, 0.0, 0.0, 0.0], [0.0, y, 0.0, 0.0],
+      [0.0, 0.0, 9.0 / 8.0, 1.0], [0.0, 0.0, -9.0 / 8.0, 0.0]]));
+  }
+
+  /// test_decompose
+  #[test]
+  fn test_decompose() {
+    let q = Quaternion::<f64>::from_axis_and_angle(&[1.0, 2.0, 3.0], 2.5);
+    assert!(q_from_rot3(&rot3(&q)).prec_eq(1e-12, &q));
+    for a in [0.0, 3.1, -3.1] { // each branch of Shepperd's method
+      for v in [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]] {
+        let q = Quaternion::<f64>::from_axis_and_angle(&v, a);
+        let p = q_from_rot3(&rot3(&q));
+        assert!(rot3(&p).prec_eq(1e-12, &rot3(&q)));
+        assert!(p[0] >= 0.0);
+      }
+    }
+    let (t, s) = ([1.0, -2.0, 3.0], [2.0, 0.5, 3.0]);
+    for c in [Conv::Col, Conv::Row] {
+      let m = Matrix4::trs(&t, &q, &s, c);
+      let (dt, dq, ds) = m.decompose(c).unwrap();
+      assert!(dt.prec_eq(1e-12, &t) && ds.prec_eq(1e-12, &s));
+      assert!(dq.prec_eq(1e-12, &q)); // w > 0
+      let n = [-2.0, 0.5, 3.0]; // reflection
+      let m = Matrix4::trs(&t, &q, &n, c);
+      let (dt, dq, ds) = m.decompose(c).unwrap();
+      assert!(ds.prec_eq(1e-12, &n));
+      assert!(Matrix4::trs(&dt, &dq, &ds, c).prec_eq(1e-12, &m));
+      let m = Matrix4::trs(&t, &q, &[-2.0, -0.5, 3.0], c); // x y flip = rot
+      let (_, _, ds) = m.decompose(c).unwrap();
+      assert!(ds.prec_eq(1e-12, &[2.0, 0.5, 3.0]));
+      let d = Decomp{t, q: q_from_rot3(&rot3(&q)), s: n,
+        shear: [0.3, -0.2, 0.4], persp: [0.2, 0.1, 0.0, 1.0]}; // persp dot (t, 1) = 1
+      let m = d.compose(c);
+      let e = m.decompose_full(c).unwrap();
+      assert!(e.t.prec_eq(1e-12, &d.t) && e.q.prec_eq(1e-12, &d.q));
+      assert!(e.s.prec_eq(1e-12, &d.s) && e.shear.prec_eq(1e-12, &d.shear));
+      assert!(e.persp.prec_eq(1e-12, &d.persp));
+      assert!(e.compose(c).prec_eq(1e-12, &m));
+    }
+    for k in [1e-3, 1e-6, 1e6] { // no absolute determinant cutoff
+      let d = Decomp{t: [0.0; 3], q, s: [k; 3], shear: [0.0; 3],
+        persp: [0.0, 0.0, 0.1, 1.0]};
+      let e = d.compose(Conv::Col).decompose_full(Conv::Col).unwrap();
+      assert!(e.persp.prec_eq(1e-12, &d.persp));
+      assert!(e.s.prec_eq(k * 1e-12, &d.s) && e.q.prec_eq(1e-12, &d.q));
+    }
+    assert_eq!(Matrix4::<f64>::scale(&[1.0, 0.0, 1.0]).decompose(Conv::Col),
+      Err(Error::Singular));
+  }
+
+  /// test_inverse
+  #[test]
+  fn test_inverse() {
+    let q = Quaternion::<f64>::from_axis_and_angle(&[1.0, 2.0, 3.0], 0.8);
+    let i = Matrix4::<f64>::identity();
+    for c in [Conv::Col, Conv::Row] {
+      let m = Matrix4::trs(&[1.0, -2.0, 3.0], &q, &[1.0; 3], c);
+      assert!(m.inverse_rigid(c).dot_m(&m).prec_eq(1e-12, &i));
+      assert!(m.inverse(c, 1e-12).unwrap().prec_eq(1e-12, &m.inv(1e-12).unwrap()));
+      let m = Matrix4::trs(&[1.0, -2.0, 3.0], &q, &[2.0, -0.5, 3.0], c);
+      let n = m.inverse_affine(c).unwrap();
+      assert!(n.dot_m(&m).prec_eq(1e-12, &i));
+      assert!(!n.inverse_rigid(c).prec_eq(1e-6, &m)); // not rigid
+      assert!(m.inverse(c, 1e-12).unwrap().prec_eq(1e-12, &n));
+      let p = Matrix4::perspective(1.0, 1.5, 0.1, 100.0, Proj{conv: c, ..Proj::GL});
+      assert!(p.inverse(c, 1e-12).unwrap().prec_eq(1e-9, &p.inv(1e-12).unwrap()));
+      assert_eq!(Matrix4::<f64>::scale(&[1.0, 0.0, 1.0]).inverse_affine(c),
+        Err(Error::Singular));
+      let k = Matrix4::trs(&[1.0, -2.0, 3.0], &q, &[1e-7, 2e-7, 3e-7], c);
+      assert!(k.inverse(c, 1e-12).unwrap().dot_m(&k).prec_eq(1e-9, &i)); // scale free
+      let mut s = k;
+      s[3][3] = 2.0; // not affine (general path)
+      assert!(s.inverse(c, 1e-12).unwrap().dot_m(&s).prec_eq(1e-6, &i));
+    }
+  }
+
+  /// test_transform
+  #[test]
+  fn test_transform() {
+    let h = std::f64::consts::FRAC_PI_2;
+    let q = Quaternion::<f64>::from_axis_and_angle(&[0.0, 0.0, 1.0], h);
+    for c in [Conv::Col, Conv::Row] {
+      let m = Matrix4::trs(&[1.0, 2.0, 3.0], &q, &[2.0, 1.0, 1.0], c);
+      assert!(m.transform_point(&[1.0, 0.0, 0.0], c).unwrap().prec_eq(1e-12,
+        &[1.0, 4.0, 3.0]));
+      assert!(m.transform_vector(&[1.0, 0.0, 0.0], c).prec_eq(1e-12,
+        &[0.0, 2.0, 0.0]));
+      let n = m.normal_matrix(c).unwrap();
+      let t = [2.0, 1.0, 0.0]; // tangent on the plane x - 2 y = 0
+      let v = [1.0, -2.0, 0.0]; // normal
+      let (t, v) = (m.transform_vector(&t, c), if c == Conv::Col { v.dot_mv(&n) }
+        else { crate::m::mn::mul(&[v], &n)[0] });
+      assert!(t.dot(&v).abs() < 1e-12); // normal keeps perpendicular
+      let p = Matrix4::perspective(h, 1.0, 1.0, 10.0, Proj{conv: c, ..Proj::GL});
+      assert!(p.transform_point(&[0.0, 0.0, -1.0], c).unwrap().prec_eq(1e-12,
+        &[0.0, 0.0, -1.0])); // near
+      assert!(p.transform_point(&[10.0, 0.0, -10.0], c).unwrap().prec_eq(1e-12,
+        &[1.0, 0.0, 1.0])); // far right edge
+      assert_eq!(p.transform_point(&[1.0, 0.0, 0.0], c),
+        Err(Error::AtInfinity)); // on the camera plane
+      assert_eq!(Matrix4::<f64>::scale(&[1.0, 0.0, 1.0]).normal_matrix(c),
+        Err(Error::Singular));
+    }
+  }
+
+  /// test_embed
+  #[test]
+  fn test_embed() {
+    let m: Matrix3<f64> = [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]];
+    let t = [10.0, 11.0, 12.0];
+    assert_eq!(Matrix4::from_m3(&m, Conv::Col), [[1.0, 2.0, 3.0, 0.0],
+      [4.0, 5.0, 6.0, 0.0], [7.0, 8.0, 9.0, 0.0], [0.0, 0.0, 0.0, 1.0]]);
+    assert_eq!(Matrix4::from_m3(&m, Conv::Row),
+      Matrix4::from_m3(&m, Conv::Col).transpose());
+    for c in [Conv::Col, Conv::Row] { // same meaning of c as affine and trs
+      let a = Matrix4::from_m3_t(&m, &t, c);
+      assert_eq!(a, crate::m::affine::affine(&m, &t, c));
+      assert_eq!(a.to_m3(c), m);
+      assert_eq!(a.translation(c), t);
+      assert_eq!(a.persp(c), [0.0, 0.0, 0.0, 1.0]);
+      let q = Quaternion::<f64>::from_axis_and_angle(&[1.0, 2.0, 3.0], 0.5);
+      let r = Matrix4::trs(&t, &q, &[1.0; 3], c);
+      assert_eq!(r, Matrix4::from_m3_t(&rot3(&q), &t, c));
+      assert_eq!(r.to_m3(c), rot3(&q));
+    }
+    let b = Matrix4::from_m3_t(&m, &t, Conv::Row);
+    assert_eq!(b.rowv4(3), [10.0, 11.0, 12.0, 1.0]);
+    assert_eq!(b.rowv4(0), [1.0, 4.0, 7.0, 0.0]); // transposed storage
+    let p = Matrix4::perspective(1.0, 1.0, 1.0, 10.0, Proj::DX);
+    assert_eq!(p.persp(Conv::Row), [0.0, 0.0, 1.0, 0.0]);
+    assert_eq!(p.persp(Conv::Row), p.transpose().persp(Conv::Col));
+    let v = t.to_v4(2.0);
+    assert_eq!(v, [10.0, 11.0, 12.0, 2.0]);
+    assert_eq!(v.to_v3(), t);
+    assert_eq!(v.div_w(), Ok([5.0, 5.5, 6.0]));
+    assert_eq!(t.to_v4(1.0).div_w(), Ok(t));
+    assert_eq!(t.to_v4(0.0).div_w(), Err(Error::AtInfinity));
+  }
+
+  /// test_unproject
+  #[test]
+  fn test_unproject() {
+    let vp = [0.0, 0.0, 800.0, 600.0];
+    for p in [Proj::GL, Proj::DX, Proj::VK] {
+      let v = Matrix4::<f64>::look_at(&[1.0, 2.0, 3.0], &[0.0, 0.0, 0.0],
+        &[0.0, 1.0, 0.0], p);
+      let r = Matrix4::perspective(1.0, 800.0 / 600.0, 0.5, 50.0, p);
+      let m = if p.conv == Conv::Col { v.dot_m(&r) } else { r.dot_m(&v) }; // view first
+      let mi = m.inverse(p.conv, 1e-12).unwrap();
+      let w = project(&[0.0, 0.0, 0.0], &m, &vp, p).unwrap();
+      assert!(w.prec_eq(1e-9, &[400.0, 300.0, w[2]])); // center of the view
+      assert!(w[2] > 0.0 && w[2] < 1.0);
+      let q = [0.3, -0.2, 0.1];
+      assert!(unproject(&project(&q, &m, &vp, p).unwrap(), &mi, &vp, p).unwrap().prec_eq(1e-9, &q));
+      let (o, d) = pick_ray(400.0, 300.0, &mi, &vp, p).unwrap();
+      let e = [-1.0, -2.0, -3.0].map(|x: f64| x / 14.0f64.sqrt());
+      assert!(d.prec_eq(1e-9, &e)); // eye to center
+      assert!(o.prec_eq(1e-9, &core::array::from_fn(|i| [1.0, 2.0, 3.0][i] +
+        0.5 * e[i]))); // on the near plane
+      let (_, d) = pick_ray(0.0, 0.0, &mi, &vp, p).unwrap(); // bottom left
+      let u = crate::m::affine::TAffine::transform_vector(&v, &d, p.conv);
+      assert!(u[0] < 0.0 && u[1] < 0.0);
+      let a = 800.0 / 600.0;
+      for r in [Matrix4::perspective_inf(1.0, a, 0.5, p),
+        Matrix4::perspective_rev(1.0, a, 0.5, 50.0, p)] { // depth 1 is not far
+        let m = if p.conv == Conv::Col { v.dot_m(&r) } else { r.dot_m(&v) };
+        let mi = m.inverse(p.conv, 1e-12).unwrap();
+        let (o, d) = pick_ray(400.0, 300.0, &mi, &vp, p).unwrap();
+        assert!(d.prec_eq(1e-9, &e)); // away from the eye
+        assert!(o.prec_eq(1e-9, &core::array::from_fn(|i| [1.0, 2.0, 3.0][i] +
+          0.5 * e[i]))); // on the near plane
+      }
+      let r = Matrix4::ortho(-4.0, 4.0, -3.0, 3.0, 0.5, 50.0, p);
+      let m = if p.conv == Conv::Col { v.dot_m(&r) } else { r.dot_m(&v) };
+      let mi = m.inverse(p.conv, 1e-12).unwrap();
+      let (o, d) = pick_ray(0.0, 300.0, &mi, &vp, p).unwrap(); // left edge
+      assert!(d.prec_eq(1e-9, &e)); // parallel to the view direction
+      let u = crate::m::affine::TAffine::transform_point(&v, &o, p.conv).unwrap();
+      assert!(u.prec_eq(1e-9, &[-4.0, 0.0, if p.hand == Hand::Left { 0.5 }
+        else { -0.5 }]));
+    }
+  }
+
+  /// test_frustum
+  #[test]
+  fn test_frustum() {
+    let h = std::f64::consts::FRAC_PI_2;
+    for p in [Proj::GL, Proj::DX, Proj::VK] {
+      let v = Matrix4::<f64>::look_at(&[0.0, 0.0, 0.0], &[0.0, 0.0, -1.0],
+        &[0.0, 1.0, 0.0], p); // looks at -z in world for both hands
+      let r = Matrix4::perspective(h, 1.0, 1.0, 10.0, p);
+      let m = if p.conv == Conv::Col { v.dot_m(&r) } else { r.dot_m(&v) };
+      let f = Frustum::new(&m, p);
+      assert!(f.contains(&[0.0, 0.0, -5.0]));
+      assert!(f.contains(&[4.0, -4.0, -5.0]));
+      assert!(!f.contains(&[6.0, 0.0, -5.0])); // right
+      assert!(!f.contains(&[0.0, 0.0, -0.5])); // before near
+      assert!(!f.contains(&[0.0, 0.0, -11.0])); // beyond far
+      assert!(!f.contains(&[0.0, 0.0, 5.0])); // behind
+      assert!((f.distance(4, &[0.0, 0.0, -3.0]) - 2.0).abs() < 1e-12);
+      assert_eq!(f.sphere(&[0.0, 0.0, -5.0], 1.0), Cull::Inside);
+      assert_eq!(f.sphere(&[0.0, 0.0, -10.0], 1.0), Cull::Intersect);
+      assert_eq!(f.sphere(&[0.0, 20.0, -5.0], 1.0), Cull::Outside);
+      assert_eq!(f.aabb(&[-1.0, -1.0, -6.0], &[1.0, 1.0, -4.0]), Cull::Inside);
+      assert_eq!(f.aabb(&[-1.0, -1.0, -6.0], &[9.0, 1.0, -4.0]), Cull::Intersect);
+      assert_eq!(f.aabb(&[-1.0, -1.0, 1.0], &[1.0, 1.0, 2.0]), Cull::Outside);
+    }
+    let r = Matrix4::<f64>::perspective_inf(h, 1.0, 1.0, Proj::GL);
+    let f = Frustum::new(&r, Proj::GL);
+    assert!(f.contains(&[0.0, 0.0, -1e9])); // no far plane
+  }
+
+  /// test_xform
+  #[test]
+  fn test_xform() {
+    let h = std::f64::consts::FRAC_PI_2;
+    let q = Quaternion::<f64>::from_axis_and_angle(&[0.0, 0.0, 1.0], h);
+    let a = Xform::<f64, ColVec>::scale(&[2.0, 1.0, 1.0]);
+    let b = Xform::<f64, ColVec>::rot_q(&q).then(&Xform::translate(&[1.0, 0.0, 0.0]));
+    let m = a.then(&b); // scale rot trans
+    assert_eq!(m.conv(), Conv::Col);
+    assert!(m.prec_eq(1e-12, &Xform::trs(&[1.0, 0.0, 0.0], &q, &[2.0, 1.0, 1.0])));
+    assert!(m.transform_point(&[1.0, 0.0, 0.0]).unwrap().prec_eq(1e-12, &[1.0, 2.0, 0.0]));
+    let r = m.to_conv::<RowVec>();
+    assert_eq!(r.conv(), Conv::Row);
+    assert_eq!(*r.m(), m.m().transpose());
+    assert!(r.transform_point(&[1.0, 0.0, 0.0]).unwrap().prec_eq(1e-12, &[1.0, 2.0, 0.0]));
+    let s = Xform::<f64, RowVec>::scale(&[2.0, 1.0, 1.0]).then(
+      &Xform::rot_q(&q)).then(&Xform::translate(&[1.0, 0.0, 0.0]));
+    assert!(s.prec_eq(1e-12, &r)); // same order in both conventions
+    assert_eq!(r.to_conv::<ColVec>(), m);
+    assert!(Xform::<f64, ColVec>::from_rot(&q).prec_eq(1e-12, &Xform::rot_q(&q)));
+    assert!(Xform::<f64, RowVec>::from_rot(&q).prec_eq(1e-12, &Xform::rot_q(&q)));
+    assert!(Matrix4::from_rot(&q).prec_eq(1e-12, &Matrix4::rot_q(&q, Conv::Col)));
+    assert!(m.inverse(1e-12).unwrap().then(&m).prec_eq(1e-12, &Xform::identity()));
+    let (t, _, k) = r.decompose().unwrap();
+    assert!(t.prec_eq(1e-12, &[1.0, 0.0, 0.0]) && k.prec_eq(1e-12, &[2.0, 1.0, 1.0]));
+    let v = Xform::<f64, RowVec>::look_at(&[0.0, 0.0, 5.0], &[0.0, 0.0, 0.0],
+      &[0.0, 1.0, 0.0], Hand::Left);
+    assert_eq!(v.into_inner(), Matrix4::look_at(&[0.0, 0.0, 5.0], &[0.0, 0.0, 0.0],
+      &[0.0, 1.0, 0.0], Proj{hand: Hand::Left, depth: Depth::NegOneOne,
+      conv: Conv::Row}));
+    let p = Xform::<f64, ColVec>::perspective(h, 1.0, 1.0, 10.0, Hand::Right,
+      Depth::NegOneOne);
+    assert_eq!(*p.m(), Matrix4::perspective(h, 1.0, 1.0, 10.0, Proj::GL));
+  }
+}
+
+/// test without std (fixed size types only, run with
+/// [--no-default-features --features libm])
+#[cfg(test)]
+mod tests_core {
+  use crate::v::{TVector, v3::{TV3, Vector3}};
+  use crate::q::{TQuaternion, Quaternion};
+  use crate::m::{TMatrix, m4::Matrix4, Conv, affine::TAffine};
+  use crate::m::xform::{Xform, ColVec};
+  use crate::w::{Vect3, Mat4, Quat};
+
+  /// test_no_std
+  #[test]
+  fn test_no_std() {
+    let h = core::f64::consts::FRAC_PI_2;
+    let x: Vector3<f64> = [1.0, 0.0, 0.0];
+    assert_eq!(x.cross(&[0.0, 1.0, 0.0]), [0.0, 0.0, 1.0]);
+    let q = Quaternion::<f64>::from_axis_and_angle(&[0.0, 0.0, 1.0], h);
+    let m = Matrix4::trs(&[1.0, 2.0, 3.0], &q, &[2.0; 3], Conv::Col);
+    assert!(m.transform_point(&x, Conv::Col).unwrap().prec_eq(1e-12, &[1.0, 4.0, 3.0]));
+    let i = m.inv(1e-12).expect("det");
+    assert!(i.dot_m(&m).prec_eq(1e-12, &Matrix4::identity()));
+    assert!((m.det() - 8.0).abs() < 1e-12);
+    let (t, r, s) = m.decompose(Conv::Col).expect("scale");
+    assert!(t.prec_eq(1e-12, &[1.0, 2.0, 3.0]) && s.prec_eq(1e-12, &[2.0; 3]));
+    assert!(r.prec_eq(1e-12, &q));
+    let v = Quat(q).rotate(&Vect3::from(x));
+    assert!(v.0.prec_eq(1e-12, &[0.0, 1.0, 0.0]));
+    assert_eq!((Mat4::<f64>::identity() * 2.0).det(), 16.0);
+    let a = Xform::<f64, ColVec>::trs(&[1.0, 2.0, 3.0], &q, &[2.0; 3]);
+    assert_eq!(*a.m(), m);
+  }
 }
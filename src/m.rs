@@ -1,22 +1,63 @@
 //! matrix
 //!
 
+pub mod mn;
 pub mod m3;
 pub mod m4;
+pub mod affine;
+pub mod cam;
+pub mod frustum;
+pub mod xform;
+#[cfg(feature = "alloc")]
+pub mod lu;
+#[cfg(feature = "alloc")]
+pub mod qr;
+#[cfg(feature = "alloc")]
+pub mod eig;
+#[cfg(feature = "alloc")]
+pub mod svd;
+#[cfg(feature = "alloc")]
+pub mod expm;
+#[cfg(feature = "alloc")]
+pub mod chol;
 
 use num::Float;
 
-use crate::v::{v3::Vector3, v4::Vector4};
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+
+#[cfg(feature = "alloc")]
+use crate::e::Error;
+use crate::e::Result;
+use crate::t::Tol;
+
+/// Conv (vector convention of transform matrices)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Conv {
+  /// column vector v' = M v (OpenGL, same as dot_mv, translation in col 3)
+  #[default]
+  Col,
+  /// row vector v' = v M (DirectX, translation in row 3, transpose of Col)
+  Row,
+}
+
+/// Polar (R, S) of the polar decomposition m = R S
+#[cfg(feature = "alloc")]
+pub type Polar<F> = (Vec<Vec<F>>, Vec<Vec<F>>);
+
+/// Svd (U, s, V^T) of the singular value decomposition m = U diag(s) V^T
+#[cfg(feature = "alloc")]
+pub type Svd<F> = (Vec<Vec<F>>, Vec<F>, Vec<Vec<F>>);
 
 /// cofactor
-pub fn cofactor<F: Float + std::fmt::Debug>(
-  m: &Vec<Vec<F>>, i: usize, j: usize) -> F {
-/*
-  assert_eq!(m.len(), m[0].len());
-  assert!(m.len() >= 2);
-*/
+/// - error when m is not square
+#[cfg(feature = "alloc")]
+pub fn cofactor<F: Float + core::fmt::Debug>(
+  m: &[Vec<F>], i: usize, j: usize) -> Result<F> {
+  check_square(m)?;
+  if m.len() == 1 { return Ok(<F>::from(1).unwrap()); }
   if m.len() == 2 {
-    return [[m[1][1]], [-m[1][0]], [-m[0][1]], [m[0][0]]][i][j];
+    return Ok([[m[1][1], -m[1][0]], [-m[0][1], m[0][0]]][i][j]);
   }
   let d = det(
     &m.iter().enumerate().flat_map(|(ri, r)|
@@ -28,99 +69,482 @@ pub fn cofactor<F: Float + std::fmt::Debug>(
         ).collect::<Vec<_>>()]
       }
     ).collect::<Vec<_>>()
-  );
-  if (i + j) % 2 == 0 { d } else { -d }
+  )?;
+  Ok(if (i + j) & 1 == 0 { d } else { -d })
 }
 
 /// transpose
-pub fn transpose<F: Float + std::fmt::Debug>(m: &Vec<Vec<F>>) -> Vec<Vec<F>> {
-  (0..m[0].len()).into_iter().map(|j|
-    (0..m.len()).into_iter().map(|i|
-      m[i][j]
-    ).collect::<Vec<_>>()
+/// - error when m is empty or ragged
+#[cfg(feature = "alloc")]
+pub fn transpose<F: Float + core::fmt::Debug>(m: &[Vec<F>]) ->
+  Result<Vec<Vec<F>>> {
+  check_rect(m)?;
+  Ok(transpose_unchecked(m))
+}
+
+/// identity n x n
+#[cfg(feature = "alloc")]
+pub fn eye<F: Float + core::fmt::Debug>(n: usize) -> Vec<Vec<F>> {
+  let o = <F>::from(0).unwrap();
+  let l = <F>::from(1).unwrap();
+  (0..n).map(|j|
+    (0..n).map(|i| if i == j { l } else { o }).collect::<Vec<_>>()
   ).collect::<Vec<_>>()
 }
 
-/// det
-pub fn det<F: Float + std::fmt::Debug>(m: &Vec<Vec<F>>) -> F {
-/*
-  assert_eq!(m.len(), m[0].len());
-  assert!(m.len() >= 2);
-*/
-  if m.len() == 2 { return m[0][0] * m[1][1] - m[0][1] * m[1][0]; } // to fast
-  let mut d = <F>::from(0).unwrap();
-  for (cj, &c) in m[0].iter().enumerate() {
-    d = d + c * cofactor(m, 0, cj); // += ops::AddAssign
+/// a dot b (rows of a x cols of b)
+/// - error when a or b is empty or ragged, or cols of a is not rows of b
+#[cfg(feature = "alloc")]
+pub fn mul<F: Float + core::fmt::Debug>(a: &[Vec<F>], b: &[Vec<F>]) ->
+  Result<Vec<Vec<F>>> {
+  let (_, n) = check_rect(a)?;
+  check_rect(b)?;
+  if b.len() != n {
+    return Err(Error::DimensionMismatch{expected: n, actual: b.len()});
   }
-  d
+  Ok(mul_unchecked(a, b))
+}
+
+/// det
+/// - LU decomposition O(n^3)
+/// - error when m is not square
+#[cfg(feature = "alloc")]
+pub fn det<F: Float + core::fmt::Debug>(m: &[Vec<F>]) -> Result<F> {
+  check_square(m)?;
+  if m.len() == 2 { return Ok(m[0][0] * m[1][1] - m[0][1] * m[1][0]); } // to fast
+  Ok(lu::LU::new(m)?.det())
 }
 
 /// inv
-/// - TODO: recursive cofactor (slow), change to LU decomposition to be faster
-/// - p: prec (assume det = 0)
-pub fn inv<F: Float + std::fmt::Debug>(m: &Vec<Vec<F>>, p: F) ->
-  Option<Vec<Vec<F>>> {
-  assert_eq!(m.len(), m[0].len());
-  assert!(m.len() >= 2);
-  let d = det(m);
-  if crate::prec_eq_f(d, p, <F>::from(0).unwrap()) { return None; }
-  Some(transpose(&m.iter().enumerate().map(|(ri, r)|
-    r.iter().enumerate().map(|(cj, _c)|
-      cofactor(m, ri, cj) / d
+/// - LU decomposition O(n^3)
+/// - p: prec (assume pivot = 0)
+/// - error when m is not square or singular
+#[cfg(feature = "alloc")]
+pub fn inv<F: Float + core::fmt::Debug>(m: &[Vec<F>], p: F) ->
+  Result<Vec<Vec<F>>> {
+  let lu = lu::LU::new(m)?;
+  if lu.is_singular(p) { return Err(Error::Singular); }
+  Ok(lu.inv())
+}
+
+/// solve m x = b
+/// - LU decomposition with partial pivoting
+/// - p: prec (assume pivot = 0)
+#[cfg(feature = "alloc")]
+pub fn solve<F: Float + core::fmt::Debug>(m: &[Vec<F>], b: &[F], p: F) ->
+  Result<Vec<F>> {
+  let lu = lu_checked(m, b.len(), p)?;
+  Ok(lu.solve(b))
+}
+
+/// solve m X = B (each column of bm is a right-hand side)
+/// - LU decomposition with partial pivoting
+/// - p: prec (assume pivot = 0)
+#[cfg(feature = "alloc")]
+pub fn solve_m<F: Float + core::fmt::Debug>(m: &[Vec<F>], bm: &[Vec<F>],
+  p: F) -> Result<Vec<Vec<F>>> {
+  let lu = lu_checked(m, bm.len(), p)?;
+  Ok(lu.solve_m(bm))
+}
+
+/// least squares solution of min |m x - b| (m: rows x cols)
+/// - Householder QR with column pivoting
+/// - rtol: relative prec for rank (dependent columns are set to 0)
+/// - error when m is empty or ragged, or rows of m is not length of b
+#[cfg(feature = "alloc")]
+pub fn lstsq<F: Float + core::fmt::Debug>(m: &[Vec<F>], b: &[F],
+  rtol: F) -> Result<Vec<F>> {
+  qr::QR::new_pivot(m)?.solve(b, rtol)
+}
+
+/// symmetric eigen decomposition m = V diag(w) V^T (returns (w, V))
+/// - cyclic Jacobi rotations (m is symmetrized)
+/// - w: descending order, V: orthonormal eigenvectors as columns
+/// - error when m is not square
+#[cfg(feature = "alloc")]
+pub fn eigen_sym<F: Float + core::fmt::Debug>(m: &[Vec<F>]) ->
+  Result<(Vec<F>, Vec<Vec<F>>)> {
+  let e = eig::Eigen::new_sym(m)?;
+  Ok((e.w, e.v))
+}
+
+/// singular value decomposition m = U diag(s) V^T (returns (U, s, V^T))
+/// - one sided Jacobi rotations (m: rows x cols, k = min(rows, cols))
+/// - U: rows x k, s: descending order, V^T: k x cols
+/// - error when m is empty or ragged
+#[cfg(feature = "alloc")]
+pub fn svd<F: Float + core::fmt::Debug>(m: &[Vec<F>]) -> Result<Svd<F>> {
+  let d = svd::SVD::new(m)?;
+  Ok((d.u, d.s, d.vt))
+}
+
+/// numerical rank (m: rows x cols)
+/// - singular value decomposition
+/// - rtol: relative prec (s_i <= rtol * s_max is treated as 0)
+/// - error when m is empty or ragged
+#[cfg(feature = "alloc")]
+pub fn rank<F: Float + core::fmt::Debug>(m: &[Vec<F>], rtol: F) ->
+  Result<usize> {
+  Ok(svd::SVD::new(m)?.rank(rtol))
+}
+
+/// Moore-Penrose pseudo inverse (m: rows x cols, returns cols x rows)
+/// - singular value decomposition
+/// - rtol: relative prec (s_i <= rtol * s_max is treated as 0)
+/// - error when m is empty or ragged
+#[cfg(feature = "alloc")]
+pub fn pinv<F: Float + core::fmt::Debug>(m: &[Vec<F>], rtol: F) ->
+  Result<Vec<Vec<F>>> {
+  Ok(svd::SVD::new(m)?.pinv(rtol))
+}
+
+/// orthonormal basis vectors of the null space (m: rows x cols)
+/// - singular value decomposition
+/// - rtol: relative prec (s_i <= rtol * s_max is treated as 0)
+/// - error when m is empty or ragged
+#[cfg(feature = "alloc")]
+pub fn null_space<F: Float + core::fmt::Debug>(m: &[Vec<F>], rtol: F) ->
+  Result<Vec<Vec<F>>> {
+  Ok(svd::SVD::new(m)?.null_space(rtol))
+}
+
+/// condition number (2-norm, infinity when singular)
+/// - singular value decomposition
+/// - error when m is empty or ragged
+#[cfg(feature = "alloc")]
+pub fn cond<F: Float + core::fmt::Debug>(m: &[Vec<F>]) -> Result<F> {
+  Ok(svd::SVD::new(m)?.cond())
+}
+
+/// polar decomposition m = R S (returns (R, S))
+/// - R: proper rotation (det = 1) nearest to m
+/// - S: symmetric stretch (not positive definite when det(m) < 0)
+/// - error when m is not square
+#[cfg(feature = "alloc")]
+pub fn polar<F: Float + core::fmt::Debug>(m: &[Vec<F>]) ->
+  Result<Polar<F>> {
+  Ok(svd::SVD::new_rot(m)?.polar())
+}
+
+/// orthonormalize columns of square m (modified Gram-Schmidt)
+/// - faster than polar but depends on the order of columns
+/// - error when m is not square or a column is zero or dependent
+///   (what remains is within 16 n eps of its length, instead of NaN)
+#[cfg(feature = "alloc")]
+pub fn orthonormalize<F: Float + core::fmt::Debug>(m: &[Vec<F>]) ->
+  Result<Vec<Vec<F>>> {
+  check_square(m)?;
+  let o = <F>::from(0).unwrap();
+  let e = <F>::epsilon() * <F>::from(16 * m.len()).unwrap();
+  let norm = |v: &[F]| v.iter().fold(o, |a, &p| a + p * p).sqrt();
+  let mut c = transpose_unchecked(m);
+  for i in 0..c.len() {
+    let n = norm(&c[i]);
+    for k in 0..i {
+      let d = c[k].iter().zip(c[i].iter()).fold(o, |a, (&p, &q)| a + p * q);
+      for j in 0..c[i].len() { c[i][j] = c[i][j] - d * c[k][j]; }
+    }
+    let d = norm(&c[i]);
+    if d <= e * n { return Err(Error::Singular); }
+    for p in c[i].iter_mut() { *p = *p / d; }
+  }
+  Ok(transpose_unchecked(&c))
+}
+
+/// deviation from proper rotation (returns (|m^T m - I|, |det(m) - 1|))
+/// - |m^T m - I|: Frobenius norm
+/// - error when m is not square
+#[cfg(feature = "alloc")]
+pub fn rot_error<F: Float + core::fmt::Debug>(m: &[Vec<F>]) ->
+  Result<(F, F)> {
+  let d = det(m)?;
+  let o = <F>::from(0).unwrap();
+  let l = <F>::from(1).unwrap();
+  let n = m.len();
+  let e = (0..n).map(|j| (0..n).map(|i| {
+    let a = (0..n).fold(o, |a, k| a + m[k][j] * m[k][i]);
+    let d = a - if i == j { l } else { o };
+    d * d
+  }).fold(o, |a, d| a + d)).fold(o, |a, d| a + d).sqrt();
+  Ok((e, (d - l).abs()))
+}
+
+/// matrix exponential
+/// - skew symmetric 3x3: Rodrigues
+/// - others: scaling and squaring with Pade approximant
+/// - error when m is not square
+#[cfg(feature = "alloc")]
+pub fn expm<F: Float + core::fmt::Debug>(m: &[Vec<F>]) ->
+  Result<Vec<Vec<F>>> {
+  expm::expm(m)
+}
+
+/// principal matrix logarithm
+/// - rotation 3x3: Rodrigues
+/// - others: inverse scaling and squaring
+#[cfg(feature = "alloc")]
+pub fn logm<F: Float + core::fmt::Debug>(m: &[Vec<F>]) ->
+  Result<Vec<Vec<F>>> {
+  expm::logm(m)
+}
+
+/// Cholesky decomposition m = L L^T (returns L)
+/// - error when m is not symmetric (n eps max |m|) or not positive definite
+#[cfg(feature = "alloc")]
+pub fn cholesky<F: Float + core::fmt::Debug>(m: &[Vec<F>]) ->
+  Result<Vec<Vec<F>>> {
+  check_sym(m)?;
+  Ok(chol::Cholesky::new(m)?.l)
+}
+
+/// solve m x = b for symmetric positive definite m
+/// - Cholesky decomposition
+/// - error when m is not symmetric or not positive definite
+#[cfg(feature = "alloc")]
+pub fn solve_spd<F: Float + core::fmt::Debug>(m: &[Vec<F>], b: &[F]) ->
+  Result<Vec<F>> {
+  check_sym(m)?;
+  if b.len() != m.len() {
+    return Err(Error::DimensionMismatch{expected: m.len(), actual: b.len()});
+  }
+  Ok(chol::Cholesky::new(m)?.solve(b))
+}
+
+/// log det of symmetric positive definite m
+/// - Cholesky decomposition (no overflow for large det)
+/// - error when m is not symmetric or not positive definite
+#[cfg(feature = "alloc")]
+pub fn log_det_spd<F: Float + core::fmt::Debug>(m: &[Vec<F>]) -> Result<F> {
+  check_sym(m)?;
+  Ok(chol::Cholesky::new(m)?.log_det())
+}
+
+/// nearest symmetric positive definite matrix
+/// - symmetrize (m + m^T) / 2 and raise eigenvalues to at least
+///   n eps max |eigenvalue| (raised further until Cholesky succeeds)
+/// - error when m is not square
+#[cfg(feature = "alloc")]
+pub fn nearest_spd<F: Float + core::fmt::Debug>(m: &[Vec<F>]) ->
+  Result<Vec<Vec<F>>> {
+  Ok(eig::Eigen::new_sym(m)?.clamp_pd())
+}
+
+/// check rectangular (returns (rows, cols))
+/// - error when m has no rows or cols, or a row length differs from the first
+#[cfg(feature = "alloc")]
+fn check_rect<F: Float + core::fmt::Debug>(m: &[Vec<F>]) ->
+  Result<(usize, usize)> {
+  let c = m.first().map_or(0, |r| r.len());
+  if c == 0 { return Err(Error::DimensionMismatch{expected: 1, actual: 0}); }
+  match m.iter().find(|r| r.len() != c) {
+  Some(r) => Err(Error::DimensionMismatch{expected: c, actual: r.len()}),
+  None => Ok((m.len(), c))
+  }
+}
+
+/// check square
+/// - error when m is empty, ragged (check_rect) or rows is not cols
+#[cfg(feature = "alloc")]
+fn check_square<F: Float + core::fmt::Debug>(m: &[Vec<F>]) -> Result<()> {
+  if m.is_empty() || m[0].is_empty() {
+    return Err(Error::NotSquare(m.len(), 0));
+  }
+  let (r, c) = check_rect(m)?;
+  if r != c { return Err(Error::NotSquare(r, c)); }
+  Ok(())
+}
+
+/// check symmetric (relative to max |m|)
+/// - error when m is not square or |m_ij - m_ji| > n eps max |m|
+#[cfg(feature = "alloc")]
+fn check_sym<F: Float + core::fmt::Debug>(m: &[Vec<F>]) -> Result<()> {
+  check_square(m)?;
+  let n = m.len();
+  let mx = m.iter().flatten().fold(<F>::from(0).unwrap(), |s, &a| s.max(a.abs()));
+  let e = <F>::epsilon() * <F>::from(n).unwrap() * mx;
+  let sym = (0..n).all(|j| (j+1..n).all(|i|
+    (m[j][i] - m[i][j]).abs() <= e)); // false for NaN
+  if !sym { return Err(Error::NotPositiveDefinite); }
+  Ok(())
+}
+
+/// transpose (m is rectangular and not empty)
+#[cfg(feature = "alloc")]
+fn transpose_unchecked<F: Float + core::fmt::Debug>(m: &[Vec<F>]) ->
+  Vec<Vec<F>> {
+  (0..m[0].len()).map(|j|
+    (0..m.len()).map(|i|
+      m[i][j]
     ).collect::<Vec<_>>()
-  ).collect::<Vec<_>>()))
+  ).collect::<Vec<_>>()
+}
+
+/// a dot b (shapes are not checked)
+#[cfg(feature = "alloc")]
+fn mul_unchecked<F: Float + core::fmt::Debug>(a: &[Vec<F>], b: &[Vec<F>]) ->
+  Vec<Vec<F>> {
+  let o = <F>::from(0).unwrap();
+  a.iter().map(|r|
+    (0..b[0].len()).map(|i|
+      r.iter().zip(b.iter()).fold(o, |s, (&p, q)| s + p * q[i])
+    ).collect::<Vec<_>>()
+  ).collect::<Vec<_>>()
+}
+
+/// LU with checking square, rows of right-hand side and singular
+#[cfg(feature = "alloc")]
+fn lu_checked<F: Float + core::fmt::Debug>(m: &[Vec<F>], n: usize, p: F) ->
+  Result<lu::LU<F>> {
+  check_square(m)?;
+  if n != m.len() {
+    return Err(Error::DimensionMismatch{expected: m.len(), actual: n});
+  }
+  let lu = lu::LU::new(m)?;
+  if lu.is_singular(p) { return Err(Error::Singular); }
+  Ok(lu)
 }
 
 /// TMatrix
-pub trait TMatrix<F: Float + std::fmt::Debug> {
-  /// constructor col major from v3 (move)
-  fn colmajor3(_m: Vec<Vector3<F>>) -> Self where Self: Sized { panic!("cm3") }
-  /// constructor row major from v3 (move)
-  fn rowmajor3(_m: Vec<Vector3<F>>) -> Self where Self: Sized { panic!("rm3") }
-  /// constructor col major from v4 (move)
-  fn colmajor4(_m: Vec<Vector4<F>>) -> Self where Self: Sized { panic!("cm4") }
-  /// constructor row major from v4 (move)
-  fn rowmajor4(_m: Vec<Vector4<F>>) -> Self where Self: Sized { panic!("rm4") }
+/// - square (N x N), the other operand is Self (mismatch does not compile)
+#[allow(clippy::ptr_arg)] // &Vec keeps the inference of new(&it.collect())
+pub trait TMatrix<F: Float + core::fmt::Debug> {
   /// constructor col major
+  #[cfg(feature = "alloc")]
   fn col_major(m: &Vec<Vec<F>>) -> Self;
   /// constructor row major
+  #[cfg(feature = "alloc")]
   fn row_major(m: &Vec<Vec<F>>) -> Self;
   /// constructor row major
+  /// - panics when m is too small (use try_new for untrusted input)
+  #[cfg(feature = "alloc")]
   fn new(m: &Vec<Vec<F>>) -> Self;
+  /// constructor row major
+  /// - error when the number of rows or cols is not the dimension
+  #[cfg(feature = "alloc")]
+  fn try_new(m: &Vec<Vec<F>>) -> Result<Self> where Self: Sized;
+  /// constructor row major from flat slice
+  /// - error when the length is not the dimension squared
+  fn try_from_slice(m: &[F]) -> Result<Self> where Self: Sized;
+  /// constructor row major from flat iterator
+  /// - error when the number of items is not the dimension squared
+  fn try_from_iter(it: impl IntoIterator<Item = F>) -> Result<Self>
+    where Self: Sized;
   /// constructor
   fn identity() -> Self;
   /// check equal with precision
-  fn prec_eq(&self, e: F, m: &impl TMatrix<F>) -> bool;
-  /// like as slice v3
-  fn mev3(&self) -> &[Vector3<F>] { panic!("mev3") }
-  /// like as slice v4
-  fn mev4(&self) -> &[Vector4<F>] { panic!("mev4") }
+  fn prec_eq(&self, e: F, m: &Self) -> bool;
+  /// check equal with tolerance (Abs Rel Ulp Mixed)
+  fn prec_eq_tol(&self, t: Tol<F>, m: &Self) -> bool {
+    let n = self.row(0).len();
+    (0..n).all(|j| crate::tol_eq(self.row(j), t, m.row(j)))
+  }
   /// m dot self
-  fn dot_m(&self, m: &impl TMatrix<F>) -> Self;
-  /// row to v3
-  fn rowv3(&self, _j: usize) -> Vector3<F> { panic!("rowv3") }
-  /// col to v3
-  fn colv3(&self, _i: usize) -> Vector3<F> { panic!("colv3") }
-  /// row to v4
-  fn rowv4(&self, _j: usize) -> Vector4<F> { panic!("rowv4") }
-  /// col to v4
-  fn colv4(&self, _i: usize) -> Vector4<F> { panic!("colv4") }
+  /// - for Conv::Col self is applied first (see xform::Xform::then)
+  fn dot_m(&self, m: &Self) -> Self;
+  /// row as slice
+  fn row(&self, j: usize) -> &[F];
   /// to_vec
+  #[cfg(feature = "alloc")]
   fn to_vec(&self) -> Vec<Vec<F>>;
   /// transpose
-  fn transpose(&self) -> Self where Self: Sized {
-    Self::col_major(&self.to_vec())
-  }
+  fn transpose(&self) -> Self where Self: Sized;
   /// det
-  fn det(&self) -> F {
-    crate::m::det(&self.to_vec())
-  }
+  fn det(&self) -> F;
   /// inv
   /// - p: prec (assume det = 0)
-  fn inv(&self, p: F) -> Option<Self> where Self: Sized {
-    match crate::m::inv(&self.to_vec(), p) {
-    None => None,
-    Some(m) => Some(Self::new(&m))
-    }
+  fn inv(&self, p: F) -> Option<Self> where Self: Sized;
+  /// deviation from proper rotation (returns (|m^T m - I|, |det(m) - 1|))
+  fn rot_error(&self) -> (F, F) where Self: Sized {
+    let o = <F>::from(0).unwrap();
+    let l = <F>::from(1).unwrap();
+    let g = self.dot_m(&self.transpose()); // m^T dot m
+    let n = self.row(0).len();
+    let e = (0..n).map(|j| g.row(j).iter().enumerate().map(|(i, &a)| {
+      let d = a - if i == j { l } else { o };
+      d * d
+    }).fold(o, |a, d| a + d)).fold(o, |a, d| a + d).sqrt();
+    (e, (self.det() - l).abs())
+  }
+  /// check proper rotation within e
+  fn is_rot(&self, e: F) -> bool where Self: Sized {
+    let (o, d) = self.rot_error();
+    o < e && d < e
+  }
+  /// symmetric eigen decomposition self = V diag(w) V^T (returns (w, V))
+  /// - w: descending order, V: orthonormal eigenvectors as columns
+  #[cfg(feature = "alloc")]
+  fn eigen_sym(&self) -> (Vec<F>, Self) where Self: Sized {
+    let e = eig::Eigen::new_sym(&self.to_vec()).expect("N x N");
+    (e.w, Self::new(&e.v))
+  }
+  /// singular value decomposition self = U diag(s) V^T (returns (U, s, V^T))
+  /// - s: descending order
+  #[cfg(feature = "alloc")]
+  fn svd(&self) -> (Self, Vec<F>, Self) where Self: Sized {
+    let d = svd::SVD::new(&self.to_vec()).expect("N x N");
+    (Self::new(&d.u), d.s, Self::new(&d.vt))
+  }
+  /// singular value decomposition with proper rotations U and V^T
+  /// - det(U) = det(V^T) = 1, the last of s takes the sign of det(self)
+  #[cfg(feature = "alloc")]
+  fn svd_rot(&self) -> (Self, Vec<F>, Self) where Self: Sized {
+    let d = svd::SVD::new_rot(&self.to_vec()).expect("N x N");
+    (Self::new(&d.u), d.s, Self::new(&d.vt))
+  }
+  /// polar decomposition self = R S (returns (R, S))
+  /// - R: proper rotation nearest to self, S: symmetric stretch
+  #[cfg(feature = "alloc")]
+  fn polar(&self) -> (Self, Self) where Self: Sized {
+    let (r, s) = svd::SVD::new_rot(&self.to_vec()).expect("N x N").polar();
+    (Self::new(&r), Self::new(&s))
+  }
+  /// orthonormalize columns (modified Gram-Schmidt)
+  /// - error when a column is zero or dependent
+  #[cfg(feature = "alloc")]
+  fn orthonormalize(&self) -> Result<Self> where Self: Sized {
+    Ok(Self::new(&crate::m::orthonormalize(&self.to_vec())?))
+  }
+  /// matrix exponential
+  #[cfg(feature = "alloc")]
+  fn expm(&self) -> Self where Self: Sized {
+    Self::new(&expm::expm(&self.to_vec()).expect("N x N"))
+  }
+  /// principal matrix logarithm
+  #[cfg(feature = "alloc")]
+  fn logm(&self) -> Result<Self> where Self: Sized {
+    Ok(Self::new(&expm::logm(&self.to_vec())?))
+  }
+  /// Cholesky decomposition self = L L^T (returns L)
+  #[cfg(feature = "alloc")]
+  fn cholesky(&self) -> Result<Self> where Self: Sized {
+    Ok(Self::new(&crate::m::cholesky(&self.to_vec())?))
+  }
+  /// nearest symmetric positive definite matrix
+  #[cfg(feature = "alloc")]
+  fn nearest_spd(&self) -> Self where Self: Sized {
+    Self::new(&eig::Eigen::new_sym(&self.to_vec()).expect("N x N").clamp_pd())
+  }
+  /// Moore-Penrose pseudo inverse
+  /// - rtol: relative prec (s_i <= rtol * s_max is treated as 0)
+  #[cfg(feature = "alloc")]
+  fn pinv(&self, rtol: F) -> Self where Self: Sized {
+    Self::new(&crate::m::pinv(&self.to_vec(), rtol).expect("N x N"))
+  }
+  /// numerical rank
+  /// - rtol: relative prec (s_i <= rtol * s_max is treated as 0)
+  #[cfg(feature = "alloc")]
+  fn rank(&self, rtol: F) -> usize {
+    crate::m::rank(&self.to_vec(), rtol).expect("N x N")
+  }
+  /// condition number (2-norm, infinity when singular)
+  #[cfg(feature = "alloc")]
+  fn cond(&self) -> F {
+    crate::m::cond(&self.to_vec()).expect("N x N")
+  }
+  /// solve m X = self (without inv)
+  /// - p: prec (assume pivot = 0)
+  #[cfg(feature = "alloc")]
+  fn solve_m(&self, m: &Self, p: F) -> Result<Self> where Self: Sized {
+    Ok(Self::new(&crate::m::solve_m(&m.to_vec(), &self.to_vec(), p)?))
   }
 }
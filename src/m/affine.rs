@@ -0,0 +1,374 @@
+//! affine transform
+//!
+
+use num::Float;
+
+use crate::e::{Error, Result};
+use crate::v::{v3::{TV3, Vector3}, v4::{TV4, Vector4}};
+use crate::q::Quaternion;
+use crate::m::{TMatrix, Conv, mn::{Matrix, transposed, mul, mul_mv}};
+use crate::m::{m3::Matrix3, m4::{TM4, Matrix4}};
+
+/// Decomp (full decomposition M = P T R H S for Conv::Col)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Decomp<F> {
+  /// translation T
+  pub t: Vector3<F>,
+  /// rotation R (normalized, w >= 0)
+  pub q: Quaternion<F>,
+  /// scale S (x is negative when M has reflection)
+  pub s: Vector3<F>,
+  /// shear H (xy, xz, yz: x' = x + xy y + xz z, y' = y + yz z)
+  pub shear: Vector3<F>,
+  /// perspective P (bottom row, \[0, 0, 0, 1\] when affine)
+  pub persp: Vector4<F>,
+}
+
+/// TAffine (affine transform constructors for Matrix4)
+/// - c: Conv::Col (v' = M v, OpenGL) or Conv::Row (v' = v M, DirectX)
+/// - angle in radian, counterclockwise about the axis (right handed)
+pub trait TAffine<F: Float + core::fmt::Debug + core::iter::Sum> {
+  /// translation
+  fn translate(t: &Vector3<F>, c: Conv) -> Self;
+  /// non uniform scale (same in both conventions)
+  fn scale(s: &Vector3<F>) -> Self;
+  /// uniform scale (same in both conventions)
+  fn scale_uniform(s: F) -> Self;
+  /// rotation about the X axis
+  fn rot_x(a: F, c: Conv) -> Self;
+  /// rotation about the Y axis
+  fn rot_y(a: F, c: Conv) -> Self;
+  /// rotation about the Z axis
+  fn rot_z(a: F, c: Conv) -> Self;
+  /// rotation about an arbitrary axis (Rodrigues, axis is normalized)
+  fn rot_axis(v: &Vector3<F>, a: F, c: Conv) -> Self;
+  /// rotation from Quaternion \[w, x, y, z\] (normalized)
+  fn rot_q(q: &Quaternion<F>, c: Conv) -> Self;
+  /// shear (x' = x + xy y + xz z, y' = yx x + y + yz z, z' = zx x + zy y + z)
+  fn shear(xy: F, xz: F, yx: F, yz: F, zx: F, zy: F, c: Conv) -> Self;
+  /// translation * rotation * scale (scale first, translation last)
+  fn trs(t: &Vector3<F>, q: &Quaternion<F>, s: &Vector3<F>, c: Conv) -> Self;
+  /// decompose into (translation, rotation, scale) (inverse of trs)
+  /// - shear and perspective are dropped (use decompose_full)
+  /// - error when a scale is 0
+  fn decompose(&self, c: Conv) -> Result<(Vector3<F>, Quaternion<F>, Vector3<F>)>;
+  /// decompose into perspective, translation, rotation, shear and scale
+  /// - M is divided by m33 first
+  /// - error when m33 or a scale is 0
+  fn decompose_full(&self, c: Conv) -> Result<Decomp<F>>;
+  /// inverse of rotation and translation (not checked)
+  /// - R^T and -R^T t
+  fn inverse_rigid(&self, c: Conv) -> Self;
+  /// inverse of affine (bottom row is not checked)
+  /// - A^-1 and -A^-1 t
+  /// - error when A is singular relative to its entries (scale free)
+  fn inverse_affine(&self, c: Conv) -> Result<Self> where Self: Sized;
+  /// inverse (rigid, affine or general)
+  /// - e: tolerance of the rotation check only (singular is scale free)
+  fn inverse(&self, c: Conv, e: F) -> Result<Self> where Self: Sized;
+  /// normal matrix (inverse transpose of the upper 3x3, same convention)
+  /// - error when the upper 3x3 is singular relative to its entries
+  fn normal_matrix(&self, c: Conv) -> Result<Matrix3<F>>;
+  /// transform point (w = 1, divided by the result w)
+  /// - error when the result w is 0 (on the camera plane of a projection)
+  fn transform_point(&self, v: &Vector3<F>, c: Conv) -> Result<Vector3<F>>;
+  /// transform direction (w = 0, translation is not applied)
+  fn transform_vector(&self, v: &Vector3<F>, c: Conv) -> Vector3<F>;
+}
+
+/// TAffine for Matrix4
+impl<F: Float + core::fmt::Debug + core::iter::Sum> TAffine<F> for Matrix4<F> {
+  /// translation
+  fn translate(t: &Vector3<F>, c: Conv) -> Self {
+    let l = <F>::from(1).unwrap();
+    affine(&diag(&[l, l, l]), t, c)
+  }
+  /// non uniform scale (same in both conventions)
+  fn scale(s: &Vector3<F>) -> Self {
+    let o = <F>::from(0).unwrap();
+    affine(&diag(s), &[o; 3], Conv::Col)
+  }
+  /// uniform scale (same in both conventions)
+  fn scale_uniform(s: F) -> Self {
+    Self::scale(&[s, s, s])
+  }
+  /// rotation about the X axis
+  fn rot_x(a: F, c: Conv) -> Self {
+    let (s, k) = a.sin_cos();
+    let o = <F>::from(0).unwrap();
+    let l = <F>::from(1).unwrap();
+    affine(&[[l, o, o], [o, k, -s], [o, s, k]], &[o; 3], c)
+  }
+  /// rotation about the Y axis
+  fn rot_y(a: F, c: Conv) -> Self {
+    let (s, k) = a.sin_cos();
+    let o = <F>::from(0).unwrap();
+    let l = <F>::from(1).unwrap();
+    affine(&[[k, o, s], [o, l, o], [-s, o, k]], &[o; 3], c)
+  }
+  /// rotation about the Z axis
+  fn rot_z(a: F, c: Conv) -> Self {
+    let (s, k) = a.sin_cos();
+    let o = <F>::from(0).unwrap();
+    let l = <F>::from(1).unwrap();
+    affine(&[[k, -s, o], [s, k, o], [o, o, l]], &[o; 3], c)
+  }
+  /// rotation about an arbitrary axis (Rodrigues, axis is normalized)
+  /// - R = I + sin(a) K + (1 - cos(a)) K^2 (K: cross product matrix of v)
+  fn rot_axis(v: &Vector3<F>, a: F, c: Conv) -> Self {
+    let o = <F>::from(0).unwrap();
+    let l = <F>::from(1).unwrap();
+    let d = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+    let (x, y, z) = (v[0] / d, v[1] / d, v[2] / d);
+    let (s, k) = a.sin_cos();
+    let t = l - k;
+    affine(&[
+      [t * x * x + k, t * x * y - s * z, t * x * z + s * y],
+      [t * x * y + s * z, t * y * y + k, t * y * z - s * x],
+      [t * x * z - s * y, t * y * z + s * x, t * z * z + k]], &[o; 3], c)
+  }
+  /// rotation from Quaternion \[w, x, y, z\] (normalized)
+  fn rot_q(q: &Quaternion<F>, c: Conv) -> Self {
+    let o = <F>::from(0).unwrap();
+    affine(&rot3(q), &[o; 3], c)
+  }
+  /// shear (x' = x + xy y + xz z, y' = yx x + y + yz z, z' = zx x + zy y + z)
+  fn shear(xy: F, xz: F, yx: F, yz: F, zx: F, zy: F, c: Conv) -> Self {
+    let o = <F>::from(0).unwrap();
+    let l = <F>::from(1).unwrap();
+    affine(&[[l, xy, xz], [yx, l, yz], [zx, zy, l]], &[o; 3], c)
+  }
+  /// translation * rotation * scale (scale first, translation last)
+  fn trs(t: &Vector3<F>, q: &Quaternion<F>, s: &Vector3<F>, c: Conv) -> Self {
+    let r = rot3(q);
+    affine(&core::array::from_fn(|j| core::array::from_fn(|i|
+      r[j][i] * s[i])), t, c)
+  }
+  /// decompose into (translation, rotation, scale) (inverse of trs)
+  /// - shear and perspective are dropped (use decompose_full)
+  /// - error when a scale is 0
+  fn decompose(&self, c: Conv) -> Result<(Vector3<F>, Quaternion<F>, Vector3<F>)> {
+    let d = self.decompose_full(c)?;
+    Ok((d.t, d.q, d.s))
+  }
+  /// decompose into perspective, translation, rotation, shear and scale
+  /// - M is divided by m33 first
+  /// - Gram-Schmidt on the columns of the linear part (Graphics Gems II)
+  /// - perspective is solved with the same factors (works at any scale)
+  /// - error when m33 or a scale is 0
+  fn decompose_full(&self, c: Conv) -> Result<Decomp<F>> {
+    let o = <F>::from(0).unwrap();
+    let l = <F>::from(1).unwrap();
+    let m = match c {
+    Conv::Col => *self,
+    Conv::Row => transposed(self)
+    };
+    if m[3][3] == o { return Err(Error::Singular); }
+    let m: Matrix4<F> = core::array::from_fn(|j| core::array::from_fn(|i|
+      m[j][i] / m[3][3]));
+    let t = [m[0][3], m[1][3], m[2][3]];
+    let mut k: Matrix3<F> = core::array::from_fn(|i| // columns
+      core::array::from_fn(|j| m[j][i]));
+    let dot = |p: &Vector3<F>, q: &Vector3<F>| p[0] * q[0] + p[1] * q[1] + p[2] * q[2];
+    let mut s = [o; 3];
+    let mut h = [o; 3];
+    for i in 0..3 {
+      for j in 0..i { // remove the components along the previous columns
+        let d = dot(&k[j], &k[i]);
+        h[i + j - 1] = d; // (0, 1) xy, (0, 2) xz, (1, 2) yz
+        k[i] = core::array::from_fn(|n| k[i][n] - d * k[j][n]);
+      }
+      s[i] = dot(&k[i], &k[i]).sqrt();
+      if s[i] == o { return Err(Error::Singular); }
+      k[i] = core::array::from_fn(|n| k[i][n] / s[i]);
+    }
+    h = [h[0] / s[1], h[1] / s[2], h[2] / s[2]];
+    let r = transposed(&k);
+    if r.det() < o { // reflection goes to the x scale
+      k[0] = k[0].map(|x| -x);
+      s[0] = -s[0];
+      h[0] = -h[0];
+      h[1] = -h[1];
+    }
+    let persp = if m[3] == [o, o, o, l] { m[3] } else {
+      // p (R H S) = m[3] xyz by the factors above (no determinant cutoff)
+      let y: Vector3<F> = core::array::from_fn(|i| m[3][i] / s[i]); // S^-1
+      let z0 = y[0]; // H^-1 (unit upper triangular)
+      let z1 = y[1] - z0 * h[0];
+      let z2 = y[2] - z0 * h[1] - z1 * h[2];
+      let p: Vector3<F> = core::array::from_fn(|i| // R^T
+        z0 * k[0][i] + z1 * k[1][i] + z2 * k[2][i]);
+      [p[0], p[1], p[2], l - dot(&p, &t)]
+    };
+    Ok(Decomp{t, q: q_from_rot3(&transposed(&k)), s, shear: h, persp})
+  }
+  /// inverse of rotation and translation (not checked)
+  /// - R^T and -R^T t
+  fn inverse_rigid(&self, c: Conv) -> Self {
+    let (r, t) = split(self, c);
+    inverse_t(&transposed(&r), &t, c)
+  }
+  /// inverse of affine (bottom row is not checked)
+  /// - A^-1 and -A^-1 t
+  /// - error when A is singular relative to its entries (scale free)
+  fn inverse_affine(&self, c: Conv) -> Result<Self> {
+    let (a, t) = split(self, c);
+    Ok(inverse_t(&inv_rel(&a)?, &t, c))
+  }
+  /// inverse (rigid, affine or general)
+  /// - rigid when the bottom row is \[0, 0, 0, 1\] and R is a rotation
+  /// - e: tolerance of the rotation check only (singular is scale free)
+  fn inverse(&self, c: Conv, e: F) -> Result<Self> {
+    let o = <F>::from(0).unwrap();
+    let l = <F>::from(1).unwrap();
+    let b = match c {
+    Conv::Col => self[3],
+    Conv::Row => core::array::from_fn(|j| self[j][3])
+    };
+    if b != [o, o, o, l] { return inv_rel(self); }
+    let (r, _) = split(self, c);
+    if r.is_rot(e) { Ok(self.inverse_rigid(c)) } else { self.inverse_affine(c) }
+  }
+  /// normal matrix (inverse transpose of the upper 3x3, same convention)
+  /// - error when the upper 3x3 is singular relative to its entries
+  fn normal_matrix(&self, c: Conv) -> Result<Matrix3<F>> {
+    let (a, _) = split(self, c);
+    let n = transposed(&inv_rel(&a)?);
+    Ok(match c {
+    Conv::Col => n,
+    Conv::Row => transposed(&n)
+    })
+  }
+  /// transform point (w = 1, divided by the result w)
+  /// - error when the result w is 0 (on the camera plane of a projection)
+  fn transform_point(&self, v: &Vector3<F>, c: Conv) -> Result<Vector3<F>> {
+    transform(self, v, <F>::from(1).unwrap(), c).div_w()
+  }
+  /// transform direction (w = 0, translation is not applied)
+  fn transform_vector(&self, v: &Vector3<F>, c: Conv) -> Vector3<F> {
+    transform(self, v, <F>::from(0).unwrap(), c).to_v3()
+  }
+}
+
+/// Decomp
+impl<F: Float + core::fmt::Debug + core::iter::Sum> Decomp<F> {
+  /// compose M = P T R H S (inverse of decompose_full)
+  pub fn compose(&self, c: Conv) -> Matrix4<F> {
+    let o = <F>::from(0).unwrap();
+    let l = <F>::from(1).unwrap();
+    let h = self.shear;
+    let rh = mul(&rot3(&self.q), &[[l, h[0], h[1]], [o, l, h[2]], [o, o, l]]);
+    let m = affine(&core::array::from_fn(|j| core::array::from_fn(|i|
+      rh[j][i] * self.s[i])), &self.t, Conv::Col);
+    let mut p = Matrix4::<F>::identity();
+    p[3] = self.persp;
+    let m = mul(&p, &m);
+    match c {
+    Conv::Col => m,
+    Conv::Row => transposed(&m)
+    }
+  }
+}
+
+/// diagonal 3x3
+fn diag<F: Float>(d: &Vector3<F>) -> Matrix3<F> {
+  let o = <F>::from(0).unwrap();
+  core::array::from_fn(|j| core::array::from_fn(|i|
+    if i == j { d[j] } else { o }))
+}
+
+/// rotation 3x3 (column vector) from Quaternion \[w, x, y, z\]
+pub fn rot3<F: Float>(q: &Quaternion<F>) -> Matrix3<F> {
+  let o = <F>::from(0).unwrap();
+  let l = <F>::from(1).unwrap();
+  let (w, x, y, z) = (q[0], q[1], q[2], q[3]);
+  let n = w * w + x * x + y * y + z * z;
+  let s = if n > o { <F>::from(2).unwrap() / n } else { o };
+  [
+    [l - s * (y * y + z * z), s * (x * y - w * z), s * (x * z + w * y)],
+    [s * (x * y + w * z), l - s * (x * x + z * z), s * (y * z - w * x)],
+    [s * (x * z - w * y), s * (y * z + w * x), l - s * (x * x + y * y)]]
+}
+
+/// (linear part, translation) of Matrix4 (as Conv::Col)
+fn split<F: Float + core::fmt::Debug>(m: &Matrix4<F>, c: Conv) ->
+  (Matrix3<F>, Vector3<F>) {
+  (m.to_m3(c), m.translation(c))
+}
+
+/// M v (Conv::Col) or v M (Conv::Row) for \[v, w\]
+fn transform<F: Float + core::fmt::Debug + core::iter::Sum>(m: &Matrix4<F>,
+  v: &Vector3<F>, w: F, c: Conv) -> Vector4<F> {
+  let v = v.to_v4(w);
+  match c {
+  Conv::Col => mul_mv(m, &v),
+  Conv::Row => mul(&[v], m)[0]
+  }
+}
+
+/// inverse with the cutoff relative to the entries
+/// - singular when |det| <= epsilon min(prod |row|, prod |col|) (Hadamard)
+fn inv_rel<F: Float + core::fmt::Debug + core::iter::Sum, const N: usize>(
+  m: &Matrix<F, N, N>) -> Result<Matrix<F, N, N>> {
+  let o = <F>::from(0).unwrap();
+  let l = <F>::from(1).unwrap();
+  let norms = |m: &Matrix<F, N, N>| m.iter().fold(l, |b, r|
+    b * r.iter().fold(o, |s, &x| s + x * x).sqrt());
+  let b = norms(m).min(norms(&transposed(m)));
+  if b == o { return Err(Error::Singular); }
+  m.inv(<F>::epsilon() * b).ok_or(Error::Singular)
+}
+
+/// affine from inverted linear part a and translation t (-a t)
+fn inverse_t<F: Float + core::fmt::Debug + core::iter::Sum>(a: &Matrix3<F>,
+  t: &Vector3<F>, c: Conv) -> Matrix4<F> {
+  affine(a, &mul_mv(a, t).map(|x| -x), c)
+}
+
+/// Quaternion \[w, x, y, z\] (w >= 0) from rotation 3x3 (column vector)
+/// - Shepperd's method (inverse of rot3)
+pub fn q_from_rot3<F: Float>(r: &Matrix3<F>) -> Quaternion<F> {
+  let o = <F>::from(0).unwrap();
+  let l = <F>::from(1).unwrap();
+  let f = <F>::from(0.25).unwrap();
+  let (tr, x, y, z) = (r[0][0] + r[1][1] + r[2][2], r[0][0], r[1][1], r[2][2]);
+  let q = if tr > o {
+    let s = (tr + l).sqrt() * <F>::from(2).unwrap();
+    [f * s, (r[2][1] - r[1][2]) / s, (r[0][2] - r[2][0]) / s,
+      (r[1][0] - r[0][1]) / s]
+  } else if x > y && x > z {
+    let s = (l + x - y - z).sqrt() * <F>::from(2).unwrap();
+    [(r[2][1] - r[1][2]) / s, f * s, (r[0][1] + r[1][0]) / s,
+      (r[0][2] + r[2][0]) / s]
+  } else if y > z {
+    let s = (l + y - x - z).sqrt() * <F>::from(2).unwrap();
+    [(r[0][2] - r[2][0]) / s, (r[0][1] + r[1][0]) / s, f * s,
+      (r[1][2] + r[2][1]) / s]
+  } else {
+    let s = (l + z - x - y).sqrt() * <F>::from(2).unwrap();
+    [(r[1][0] - r[0][1]) / s, (r[0][2] + r[2][0]) / s,
+      (r[1][2] + r[2][1]) / s, f * s]
+  };
+  let d = (q[0] * q[0] + q[1] * q[1] + q[2] * q[2] + q[3] * q[3]).sqrt();
+  let d = if q[0] < o { -d } else { d };
+  q.map(|x| x / d)
+}
+
+/// compose Matrix4 from linear part r and translation t (column vector)
+/// - Conv::Row returns the transpose
+pub fn affine<F: Float + core::fmt::Debug>(r: &Matrix3<F>, t: &Vector3<F>,
+  c: Conv) -> Matrix4<F> {
+  let o = <F>::from(0).unwrap();
+  let l = <F>::from(1).unwrap();
+  let m = core::array::from_fn(|j| core::array::from_fn(|i|
+    match (j, i) {
+    (3, 3) => l,
+    (3, _) => o,
+    (_, 3) => t[j],
+    _ => r[j][i]
+    }));
+  match c {
+  Conv::Col => m,
+  Conv::Row => transposed(&m)
+  }
+}
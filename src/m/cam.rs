@@ -0,0 +1,268 @@
+//! camera view and projection
+//!
+
+use num::Float;
+
+use crate::e::Result;
+use crate::v::{TVector, v3::{TV3, Vector3}, v4::{TV4, Vector4}};
+use crate::m::{Conv, mn::transposed, m4::Matrix4, affine::TAffine};
+
+/// Hand (handedness of the view space)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Hand {
+  /// left handed (looks at +z, DirectX)
+  Left,
+  /// right handed (looks at -z, OpenGL)
+  #[default]
+  Right,
+}
+
+/// Depth (range of the normalized device depth)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Depth {
+  /// \[0, 1\] (Direct3D Vulkan Metal)
+  ZeroOne,
+  /// \[-1, 1\] (OpenGL)
+  #[default]
+  NegOneOne,
+}
+
+/// Proj (conventions of view and projection matrices)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Proj {
+  /// handedness
+  pub hand: Hand,
+  /// depth range
+  pub depth: Depth,
+  /// vector convention (Row is the transpose of Col)
+  pub conv: Conv,
+}
+
+/// Proj
+impl Proj {
+  /// OpenGL (right handed, \[-1, 1\], column vector)
+  pub const GL: Proj = Proj{hand: Hand::Right, depth: Depth::NegOneOne,
+    conv: Conv::Col};
+  /// Direct3D (left handed, \[0, 1\], row vector)
+  pub const DX: Proj = Proj{hand: Hand::Left, depth: Depth::ZeroOne,
+    conv: Conv::Row};
+  /// Vulkan (right handed, \[0, 1\], column vector, y is not flipped)
+  pub const VK: Proj = Proj{hand: Hand::Right, depth: Depth::ZeroOne,
+    conv: Conv::Col};
+}
+
+/// TCam (camera constructors for Matrix4)
+/// - near and far are positive distances along the view direction
+/// - fovy: vertical field of view in radian, aspect: width / height
+pub trait TCam<F: Float + core::fmt::Debug> {
+  /// view matrix (depth of p is not used)
+  /// - eye must differ from center (NaN otherwise)
+  /// - when up is (nearly) parallel to center - eye, the world axis least
+  ///   aligned with the view direction is used as up instead
+  fn look_at(eye: &Vector3<F>, center: &Vector3<F>, up: &Vector3<F>,
+    p: Proj) -> Self;
+  /// perspective from field of view
+  fn perspective(fovy: F, aspect: F, near: F, far: F, p: Proj) -> Self;
+  /// perspective from frustum bounds on the near plane
+  fn frustum(l: F, r: F, b: F, t: F, near: F, far: F, p: Proj) -> Self;
+  /// orthographic
+  fn ortho(l: F, r: F, b: F, t: F, near: F, far: F, p: Proj) -> Self;
+  /// perspective with infinite far plane
+  fn perspective_inf(fovy: F, aspect: F, near: F, p: Proj) -> Self;
+  /// reversed Z perspective (near maps to 1, far maps to the lower bound)
+  fn perspective_rev(fovy: F, aspect: F, near: F, far: F, p: Proj) -> Self;
+}
+
+/// TCam for Matrix4
+impl<F: Float + core::fmt::Debug> TCam<F> for Matrix4<F> {
+  /// view matrix (depth of p is not used)
+  fn look_at(eye: &Vector3<F>, center: &Vector3<F>, up: &Vector3<F>,
+    p: Proj) -> Self {
+    let o = <F>::from(0).unwrap();
+    let l = <F>::from(1).unwrap();
+    let f = normalize(&core::array::from_fn(|i| center[i] - eye[i]));
+    let up = &safe_up(&f, up);
+    let (s, z) = match p.hand {
+    Hand::Right => (normalize(&f.cross(up)), f.map(|x| -x)),
+    Hand::Left => (normalize(&up.cross(&f)), f)
+    };
+    let u = z.cross(&s);
+    let dot = |a: &Vector3<F>| -(a[0] * eye[0] + a[1] * eye[1] + a[2] * eye[2]);
+    conv([
+      [s[0], s[1], s[2], dot(&s)],
+      [u[0], u[1], u[2], dot(&u)],
+      [z[0], z[1], z[2], dot(&z)],
+      [o, o, o, l]], p.conv)
+  }
+  /// perspective from field of view
+  fn perspective(fovy: F, aspect: F, near: F, far: F, p: Proj) -> Self {
+    let (x, y) = scale_fov(fovy, aspect);
+    let (a, b) = depth(near, far, p);
+    projective(x, y, a, b, p)
+  }
+  /// perspective from frustum bounds on the near plane
+  fn frustum(l: F, r: F, b: F, t: F, near: F, far: F, p: Proj) -> Self {
+    let (x, y, z) = (r - l, t - b, <F>::from(2).unwrap() * near);
+    let (da, db) = depth(near, far, p);
+    let mut m = projective(z / x, z / y, da, db, Proj{conv: Conv::Col, ..p});
+    let s = -sgn::<F>(p.hand);
+    (m[0][2], m[1][2]) = (s * (r + l) / x, s * (t + b) / y);
+    conv(m, p.conv)
+  }
+  /// orthographic
+  fn ortho(l: F, r: F, b: F, t: F, near: F, far: F, p: Proj) -> Self {
+    let o = <F>::from(0).unwrap();
+    let w = <F>::from(1).unwrap();
+    let two = <F>::from(2).unwrap();
+    let s = sgn::<F>(p.hand);
+    let (a, c) = match p.depth {
+    Depth::ZeroOne => (s / (far - near), -near / (far - near)),
+    Depth::NegOneOne => (s * two / (far - near), -(far + near) / (far - near))
+    };
+    conv([
+      [two / (r - l), o, o, -(r + l) / (r - l)],
+      [o, two / (t - b), o, -(t + b) / (t - b)],
+      [o, o, a, c],
+      [o, o, o, w]], p.conv)
+  }
+  /// perspective with infinite far plane
+  fn perspective_inf(fovy: F, aspect: F, near: F, p: Proj) -> Self {
+    let (x, y) = scale_fov(fovy, aspect);
+    let k = match p.depth {
+    Depth::ZeroOne => <F>::from(1).unwrap(),
+    Depth::NegOneOne => <F>::from(2).unwrap()
+    };
+    projective(x, y, sgn(p.hand), -k * near, p)
+  }
+  /// reversed Z perspective (near maps to 1, far maps to the lower bound)
+  /// - use with Depth::ZeroOne and a floating point depth buffer
+  fn perspective_rev(fovy: F, aspect: F, near: F, far: F, p: Proj) -> Self {
+    let (x, y) = scale_fov(fovy, aspect);
+    let (a, b) = depth(far, near, p); // swap near and far
+    projective(x, y, a, b, p)
+  }
+}
+
+/// window coordinates from world point v
+/// - m: view projection, vp: viewport \[x, y, width, height\]
+/// - window y goes up from vp y, window depth is \[0, 1\] for both Depth
+/// - error when v is on the camera plane (w = 0)
+pub fn project<F: Float + core::fmt::Debug + core::iter::Sum>(v: &Vector3<F>,
+  m: &Matrix4<F>, vp: &Vector4<F>, p: Proj) -> Result<Vector3<F>> {
+  let l = <F>::from(1).unwrap();
+  let h = <F>::from(0.5).unwrap();
+  let n = m.transform_point(v, p.conv)?;
+  let z = match p.depth {
+  Depth::ZeroOne => n[2],
+  Depth::NegOneOne => (n[2] + l) * h
+  };
+  Ok([vp[0] + (n[0] + l) * h * vp[2], vp[1] + (n[1] + l) * h * vp[3], z])
+}
+
+/// world point from window coordinates w (inverse of project)
+/// - mi: inverse of view projection, vp: viewport \[x, y, width, height\]
+/// - error when w maps to infinity (far plane of perspective_inf)
+pub fn unproject<F: Float + core::fmt::Debug + core::iter::Sum>(w: &Vector3<F>,
+  mi: &Matrix4<F>, vp: &Vector4<F>, p: Proj) -> Result<Vector3<F>> {
+  let l = <F>::from(1).unwrap();
+  let two = <F>::from(2).unwrap();
+  let z = match p.depth {
+  Depth::ZeroOne => w[2],
+  Depth::NegOneOne => w[2] * two - l
+  };
+  let n = [(w[0] - vp[0]) / vp[2] * two - l, (w[1] - vp[1]) / vp[3] * two - l, z];
+  mi.transform_point(&n, p.conv)
+}
+
+/// world ray (origin on the near plane, normalized direction) through pixel
+/// - mi: inverse of view projection, vp: viewport \[x, y, width, height\]
+/// - direction points away from the eye for every perspective builder
+///   (perspective_inf, perspective_rev included)
+/// - orthographic (no eye): window depth 0 is near
+pub fn pick_ray<F: Float + core::fmt::Debug + core::iter::Sum>(x: F, y: F,
+  mi: &Matrix4<F>, vp: &Vector4<F>, p: Proj) ->
+  Result<(Vector3<F>, Vector3<F>)> {
+  let o = <F>::from(0).unwrap();
+  let l = <F>::from(1).unwrap();
+  let at = |z: F| unproject(&[x, y, z], mi, vp, p);
+  let m = at(<F>::from(0.5).unwrap())?; // finite for every builder
+  let e: Vector4<F> = match p.conv { // eye is mi dot \[0, 0, 1, 0\]
+  Conv::Col => core::array::from_fn(|j| mi[j][2]),
+  Conv::Row => mi[2]
+  };
+  let sub = |a: &Vector3<F>, b: &Vector3<F>| core::array::from_fn(|i| a[i] - b[i]);
+  match e.div_w() {
+  Ok(e) => { // perspective
+    let dist = |a: &Vector3<F>| { let d: Vector3<F> = sub(a, &e); d.dot(&d) };
+    let org = [at(o), at(l)].into_iter().filter_map(|a| a.ok())
+      .fold(m, |n, a| if dist(&a) < dist(&n) { a } else { n }); // near plane
+    Ok((org, normalize(&sub(&m, &e))))
+  },
+  Err(_) => { // orthographic
+    let org = at(o)?;
+    Ok((org, normalize(&sub(&at(l)?, &org))))
+  }
+  }
+}
+
+/// +1 (Left) or -1 (Right) (sign of z in front of the camera)
+fn sgn<F: Float>(h: Hand) -> F {
+  let l = <F>::from(1).unwrap();
+  match h {
+  Hand::Left => l,
+  Hand::Right => -l
+  }
+}
+
+/// normalize v
+fn normalize<F: Float>(v: &Vector3<F>) -> Vector3<F> {
+  let d = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+  [v[0] / d, v[1] / d, v[2] / d]
+}
+
+/// up unless (nearly) parallel to unit f (then the axis least aligned with f)
+/// - sin(angle) <= sqrt(eps) keeps f x up accurate to sqrt(eps) at worst
+fn safe_up<F: Float + core::fmt::Debug>(f: &Vector3<F>, up: &Vector3<F>) ->
+  Vector3<F> {
+  let n2 = |v: &Vector3<F>| v[0] * v[0] + v[1] * v[1] + v[2] * v[2];
+  if n2(&f.cross(up)) > <F>::epsilon() * n2(up) { return *up; }
+  let i = (0..3).min_by(|&a, &b|
+    crate::total_cmp_f(f[a].abs(), f[b].abs())).unwrap_or(0);
+  core::array::from_fn(|j| <F>::from(if i == j { 1 } else { 0 }).unwrap())
+}
+
+/// (x scale, y scale) from field of view
+fn scale_fov<F: Float>(fovy: F, aspect: F) -> (F, F) {
+  let y = <F>::from(1).unwrap() / (fovy / <F>::from(2).unwrap()).tan();
+  (y / aspect, y)
+}
+
+/// (m22, m23) mapping view depth near to the lower bound and far to 1
+fn depth<F: Float>(near: F, far: F, p: Proj) -> (F, F) {
+  let s = sgn::<F>(p.hand);
+  let d = far - near;
+  match p.depth {
+  Depth::ZeroOne => (s * far / d, -far * near / d),
+  Depth::NegOneOne => (s * (far + near) / d,
+    -<F>::from(2).unwrap() * far * near / d)
+  }
+}
+
+/// projective matrix (w = s z)
+fn projective<F: Float + core::fmt::Debug>(x: F, y: F, a: F, b: F, p: Proj) ->
+  Matrix4<F> {
+  let o = <F>::from(0).unwrap();
+  conv([
+    [x, o, o, o],
+    [o, y, o, o],
+    [o, o, a, b],
+    [o, o, sgn(p.hand), o]], p.conv)
+}
+
+/// transpose for Conv::Row
+fn conv<F: Float + core::fmt::Debug>(m: Matrix4<F>, c: Conv) -> Matrix4<F> {
+  match c {
+  Conv::Col => m,
+  Conv::Row => transposed(&m)
+  }
+}
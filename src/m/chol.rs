@@ -0,0 +1,124 @@
+//! Cholesky and LDL^T decomposition
+//!
+
+use num::Float;
+
+use alloc::{vec, vec::Vec};
+
+use crate::e::{Error, Result};
+use crate::m::{eye, check_square, transpose_unchecked};
+
+/// Cholesky decomposition of symmetric positive definite m (m = L L^T)
+/// - only the lower triangle of m is referenced
+#[derive(Debug, Clone)]
+pub struct Cholesky<F: Float + core::fmt::Debug> {
+  /// L (lower triangular with positive diagonal)
+  pub l: Vec<Vec<F>>,
+}
+
+/// Cholesky
+impl<F: Float + core::fmt::Debug> Cholesky<F> {
+  /// constructor (decompose m)
+  /// - error when m is not positive definite (instead of NaN)
+  pub fn new(m: &[Vec<F>]) -> Result<Self> {
+    check_square(m)?;
+    let n = m.len();
+    let o = <F>::from(0).unwrap();
+    let mut l = vec![vec![o; n]; n];
+    for j in 0..n {
+      let d = (0..j).fold(m[j][j], |s, k| s - l[j][k] * l[j][k]);
+      if d <= o || d.is_nan() { return Err(Error::NotPositiveDefinite); }
+      l[j][j] = d.sqrt();
+      for i in j+1..n {
+        let s = (0..j).fold(m[i][j], |s, k| s - l[i][k] * l[j][k]);
+        l[i][j] = s / l[j][j];
+      }
+    }
+    Ok(Cholesky{l})
+  }
+  /// solve m x = b
+  pub fn solve(&self, b: &[F]) -> Vec<F> {
+    let n = self.l.len();
+    let mut x = b.to_vec();
+    for j in 0..n { // L y = b
+      for k in 0..j { x[j] = x[j] - self.l[j][k] * x[k]; }
+      x[j] = x[j] / self.l[j][j];
+    }
+    for j in (0..n).rev() { // L^T x = y
+      for k in j+1..n { x[j] = x[j] - self.l[k][j] * x[k]; }
+      x[j] = x[j] / self.l[j][j];
+    }
+    x
+  }
+  /// solve m X = B (each column of bm is a right-hand side)
+  pub fn solve_m(&self, bm: &[Vec<F>]) -> Vec<Vec<F>> {
+    transpose_unchecked(&transpose_unchecked(bm).iter().map(|b|
+      self.solve(b)).collect::<Vec<_>>())
+  }
+  /// inv
+  pub fn inv(&self) -> Vec<Vec<F>> {
+    self.solve_m(&eye(self.l.len()))
+  }
+  /// log det (= 2 sum log l_ii, no overflow)
+  pub fn log_det(&self) -> F {
+    let t = <F>::from(2).unwrap();
+    self.l.iter().enumerate().fold(<F>::from(0).unwrap(), |s, (j, r)|
+      s + t * r[j].ln())
+  }
+}
+
+/// LDL^T decomposition of symmetric m (m = L D L^T, without pivoting)
+/// - only the lower triangle of m is referenced
+/// - no square roots, also works for some indefinite m
+#[derive(Debug, Clone)]
+pub struct LDLT<F: Float + core::fmt::Debug> {
+  /// L (unit lower triangular)
+  pub l: Vec<Vec<F>>,
+  /// diagonal of D
+  pub d: Vec<F>,
+}
+
+/// LDLT
+impl<F: Float + core::fmt::Debug> LDLT<F> {
+  /// constructor (decompose m)
+  /// - error when a pivot is 0 (instead of NaN)
+  pub fn new(m: &[Vec<F>]) -> Result<Self> {
+    check_square(m)?;
+    let n = m.len();
+    let o = <F>::from(0).unwrap();
+    let mut l = eye(n);
+    let mut d = vec![o; n];
+    for j in 0..n {
+      d[j] = (0..j).fold(m[j][j], |s, k|
+        s - l[j][k] * l[j][k] * d[k]);
+      if d[j] == o || d[j].is_nan() { return Err(Error::Singular); }
+      for i in j+1..n {
+        let s = (0..j).fold(m[i][j], |s, k|
+          s - l[i][k] * l[j][k] * d[k]);
+        l[i][j] = s / d[j];
+      }
+    }
+    Ok(LDLT{l, d})
+  }
+  /// check positive definite (all of D > 0)
+  pub fn is_pd(&self) -> bool {
+    self.d.iter().all(|&d| d > <F>::from(0).unwrap())
+  }
+  /// solve m x = b
+  pub fn solve(&self, b: &[F]) -> Vec<F> {
+    let n = self.l.len();
+    let mut x = b.to_vec();
+    for j in 0..n { // L y = b
+      for k in 0..j { x[j] = x[j] - self.l[j][k] * x[k]; }
+    }
+    for (x, &d) in x.iter_mut().zip(self.d.iter()) { *x = *x / d; } // D z = y
+    for j in (0..n).rev() { // L^T x = z
+      for k in j+1..n { x[j] = x[j] - self.l[k][j] * x[k]; }
+    }
+    x
+  }
+  /// log |det| (= sum log |d_i|)
+  pub fn log_det(&self) -> F {
+    self.d.iter().fold(<F>::from(0).unwrap(), |s, &d| s + d.abs().ln())
+  }
+}
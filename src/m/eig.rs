@@ -0,0 +1,111 @@
+//! eigen decomposition
+//!
+
+use num::Float;
+
+use alloc::vec::Vec;
+
+use crate::e::Result;
+use crate::m::{check_square, chol::Cholesky};
+
+/// symmetric eigen decomposition by cyclic Jacobi rotations
+/// - m = V diag(w) V^T
+#[derive(Debug, Clone)]
+pub struct Eigen<F: Float + core::fmt::Debug> {
+  /// eigenvalues (descending order)
+  pub w: Vec<F>,
+  /// orthonormal eigenvectors as columns (largest component is positive)
+  pub v: Vec<Vec<F>>,
+}
+
+/// Eigen
+impl<F: Float + core::fmt::Debug> Eigen<F> {
+  /// constructor (decompose square m)
+  /// - m is symmetrized as (m + m^T) / 2
+  /// - robust to repeated eigenvalues
+  /// - error when m is not square
+  pub fn new_sym(m: &[Vec<F>]) -> Result<Self> {
+    check_square(m)?;
+    let n = m.len();
+    let o = <F>::from(0).unwrap();
+    let l = <F>::from(1).unwrap();
+    let h = <F>::from(0.5).unwrap();
+    let mut a = (0..n).map(|j| (0..n).map(|i|
+      (m[j][i] + m[i][j]) * h).collect::<Vec<_>>()).collect::<Vec<_>>();
+    let mut v = (0..n).map(|j| (0..n).map(|i|
+      if i == j { l } else { o }).collect::<Vec<_>>()).collect::<Vec<_>>();
+    let nrm = a.iter().flatten().fold(o, |s, &x| s + x * x);
+    let tol = <F>::epsilon() * <F>::epsilon() * nrm;
+    for _sweep in 0..64 {
+      let off = (0..n).map(|j| (j+1..n).map(|i|
+        a[j][i] * a[j][i]).fold(o, |s, x| s + x)).fold(o, |s, x| s + x);
+      if off <= tol { break; }
+      for p in 0..n {
+        for q in p+1..n {
+          if a[p][q] == o { continue; }
+          let theta = (a[q][q] - a[p][p]) / (a[p][q] + a[p][q]);
+          let t = l / (theta.abs() + (theta * theta + l).sqrt());
+          let t = if theta < o { -t } else { t };
+          let c = l / (t * t + l).sqrt();
+          let s = t * c;
+          for r in a.iter_mut() { // a J
+            let (akp, akq) = (r[p], r[q]);
+            r[p] = c * akp - s * akq;
+            r[q] = s * akp + c * akq;
+          }
+          #[allow(clippy::needless_range_loop)] // rows p and q of a
+          for k in 0..n { // J^T a J
+            let (apk, aqk) = (a[p][k], a[q][k]);
+            a[p][k] = c * apk - s * aqk;
+            a[q][k] = s * apk + c * aqk;
+          }
+          for r in v.iter_mut() { // v J
+            let (vkp, vkq) = (r[p], r[q]);
+            r[p] = c * vkp - s * vkq;
+            r[q] = s * vkp + c * vkq;
+          }
+        }
+      }
+    }
+    let mut idx = (0..n).collect::<Vec<_>>();
+    idx.sort_by(|&i, &j| crate::total_cmp_f(a[j][j], a[i][i]));
+    let w = idx.iter().map(|&i| a[i][i]).collect::<Vec<_>>();
+    let sgn = idx.iter().map(|&i| {
+      let k = (0..n).fold(0, |k, j|
+        if v[j][i].abs() > v[k][i].abs() { j } else { k });
+      if v[k][i] < o { -l } else { l }
+    }).collect::<Vec<_>>();
+    let v = (0..n).map(|j|
+      idx.iter().zip(sgn.iter()).map(|(&i, &s)| v[j][i] * s).collect()
+    ).collect::<Vec<_>>();
+    Ok(Eigen{w, v})
+  }
+  /// recompose V diag(max(w, f)) V^T (exact symmetric)
+  /// - f starts at n eps max |w| and is doubled until Cholesky succeeds
+  ///   (rounding in the recomposition may undercut the first f)
+  pub fn clamp_pd(&self) -> Vec<Vec<F>> {
+    let o = <F>::from(0).unwrap();
+    let n = self.w.len();
+    let mx = self.w.iter().fold(o, |s, &w| s.max(w.abs()));
+    let mut f = <F>::epsilon() * <F>::from(n).unwrap() * mx.max(<F>::min_positive_value());
+    let mut r = self.recompose(f);
+    for _ in 0..64 { // f grows to 2^64 n eps max |w| at most
+      if Cholesky::new(&r).is_ok() { break; }
+      f = f + f;
+      r = self.recompose(f);
+    }
+    r
+  }
+  /// recompose V diag(max(w, f)) V^T (exact symmetric)
+  fn recompose(&self, f: F) -> Vec<Vec<F>> {
+    let o = <F>::from(0).unwrap();
+    let n = self.w.len();
+    let w = self.w.iter().map(|&w| w.max(f)).collect::<Vec<_>>();
+    let v = &self.v;
+    let r = (0..n).map(|j| (0..n).map(|i|
+      (0..n).fold(o, |a, k| a + v[j][k] * w[k] * v[i][k])
+    ).collect::<Vec<_>>()).collect::<Vec<_>>();
+    (0..n).map(|j| (0..n).map(|i|
+      if i < j { r[i][j] } else { r[j][i] }).collect()).collect()
+  }
+}
@@ -0,0 +1,167 @@
+//! matrix exponential and logarithm
+//!
+
+use num::Float;
+
+use alloc::{vec, vec::Vec};
+
+use crate::e::{Error, Result};
+use crate::m::{eye, check_square, lu::LU};
+use crate::m::{transpose_unchecked, mul_unchecked};
+
+/// matrix exponential
+/// - skew symmetric 3x3: Rodrigues
+/// - others: scaling and squaring with Pade approximant (6, 6)
+/// - error when m is not square
+pub fn expm<F: Float + core::fmt::Debug>(m: &[Vec<F>]) ->
+  Result<Vec<Vec<F>>> {
+  check_square(m)?;
+  if m.len() == 3 && is_skew(m) { return Ok(exp_rodrigues(m)); }
+  let n = m.len();
+  let l = <F>::from(1).unwrap();
+  let h = <F>::from(0.5).unwrap();
+  let nrm = norm_inf(m);
+  let s = if nrm > h { (nrm / h).log2().ceil().to_i32().unwrap() } else { 0 };
+  let a = scale(m, <F>::from(2).unwrap().powi(-s));
+  let q = 6;
+  let mut c = l;
+  let mut x = eye(n);
+  let mut nm = eye(n);
+  let mut dm = eye(n);
+  for k in 1..=q {
+    c = c * <F>::from(q - k + 1).unwrap() / <F>::from(k * (2 * q - k + 1)).unwrap();
+    x = mul_unchecked(&a, &x);
+    let cx = scale(&x, c);
+    nm = add(&nm, &cx);
+    dm = if k % 2 == 0 { add(&dm, &cx) } else { add(&dm, &scale(&cx, -l)) };
+  }
+  let mut e = LU::new(&dm)?.solve_m(&nm);
+  for _ in 0..s { e = mul_unchecked(&e, &e); }
+  Ok(e)
+}
+
+/// principal matrix logarithm
+/// - rotation 3x3: Rodrigues (returns skew symmetric)
+/// - others: inverse scaling and squaring (Denman-Beavers square roots)
+/// - error when real principal logarithm does not exist
+pub fn logm<F: Float + core::fmt::Debug>(m: &[Vec<F>]) ->
+  Result<Vec<Vec<F>>> {
+  check_square(m)?;
+  let n = m.len();
+  let o = <F>::from(0).unwrap();
+  let l = <F>::from(1).unwrap();
+  let eps = <F>::epsilon();
+  if n == 3 {
+    let (e, d) = crate::m::rot_error(m)?;
+    if e < eps.sqrt() && d < eps.sqrt() { return Ok(log_rodrigues(m)); }
+  }
+  if LU::new(m)?.det() <= o { return Err(Error::NoRealLog); }
+  let i = eye(n);
+  let q = <F>::from(0.25).unwrap();
+  let mut y = m.to_vec();
+  let mut k = 0;
+  while norm_inf(&sub(&y, &i)) > q {
+    if k >= 64 { return Err(Error::NoRealLog); }
+    y = sqrtm(&y)?;
+    k += 1;
+  }
+  // log(y) = 2 atanh(z), z = (y - I)(y + I)^-1 (Gregory series)
+  let z = LU::new(&add(&y, &i))?.solve_m(&sub(&y, &i));
+  let z2 = mul_unchecked(&z, &z);
+  let mut t = z.clone();
+  let mut g = z;
+  for j in 1..64 {
+    t = mul_unchecked(&t, &z2);
+    let c = scale(&t, l / <F>::from(2 * j + 1).unwrap());
+    g = add(&g, &c);
+    if norm_inf(&c) <= eps * norm_inf(&g) { break; }
+  }
+  Ok(scale(&g, <F>::from(2).unwrap().powi(k + 1)))
+}
+
+/// principal square root (Denman-Beavers iteration)
+fn sqrtm<F: Float + core::fmt::Debug>(m: &[Vec<F>]) -> Result<Vec<Vec<F>>> {
+  let h = <F>::from(0.5).unwrap();
+  let tol = <F>::epsilon() * <F>::from(m.len() * 4).unwrap();
+  let mut y = m.to_vec();
+  let mut z = eye(m.len());
+  for _ in 0..100 {
+    let (ly, lz) = (LU::new(&y)?, LU::new(&z)?);
+    if ly.is_singular(<F>::min_positive_value())
+    || lz.is_singular(<F>::min_positive_value()) {
+      return Err(Error::NoRealLog);
+    }
+    let yn = scale(&add(&y, &lz.inv()), h);
+    z = scale(&add(&z, &ly.inv()), h);
+    let d = norm_inf(&sub(&yn, &y));
+    y = yn;
+    if d <= tol * norm_inf(&y) { return Ok(y); }
+  }
+  Err(Error::NoRealLog)
+}
+
+/// exp of skew symmetric 3x3 (Rodrigues)
+fn exp_rodrigues<F: Float + core::fmt::Debug>(m: &[Vec<F>]) -> Vec<Vec<F>> {
+  let l = <F>::from(1).unwrap();
+  let w = [m[2][1], m[0][2], m[1][0]];
+  let t = w.iter().fold(<F>::from(0).unwrap(), |s, &a| s + a * a).sqrt();
+  if t <= <F>::epsilon() { return add(&eye(3), m); }
+  let k = scale(m, l / t);
+  add(&add(&eye(3), &scale(&k, t.sin())),
+    &scale(&mul_unchecked(&k, &k), l - t.cos()))
+}
+
+/// log of rotation 3x3 (Rodrigues)
+fn log_rodrigues<F: Float + core::fmt::Debug>(m: &[Vec<F>]) -> Vec<Vec<F>> {
+  let o = <F>::from(0).unwrap();
+  let l = <F>::from(1).unwrap();
+  let h = <F>::from(0.5).unwrap();
+  let c = ((m[0][0] + m[1][1] + m[2][2] - l) * h).max(-l).min(l);
+  let t = c.acos();
+  let a = scale(&sub(m, &transpose_unchecked(m)), h); // sin t K
+  if t <= <F>::epsilon().sqrt() { return a; }
+  if <F>::from(core::f64::consts::PI).unwrap() - t > <F>::epsilon().sqrt() {
+    return scale(&a, t / t.sin());
+  }
+  // t = pi: (m + I) / 2 = n n^T
+  let b = scale(&add(m, &eye(3)), h);
+  let j = (0..3).fold(0, |j, i| if b[i][i] > b[j][j] { i } else { j });
+  let d = b[j][j].sqrt();
+  let w = (0..3).map(|i| b[i][j] / d * t).collect::<Vec<_>>();
+  vec![
+    vec![o, -w[2], w[1]],
+    vec![w[2], o, -w[0]],
+    vec![-w[1], w[0], o]]
+}
+
+/// check skew symmetric
+fn is_skew<F: Float + core::fmt::Debug>(m: &[Vec<F>]) -> bool {
+  let e = <F>::epsilon() * (norm_inf(m) + <F>::from(1).unwrap());
+  (0..m.len()).all(|j| (0..m.len()).all(|i|
+    (m[j][i] + m[i][j]).abs() <= e))
+}
+
+/// infinity norm (max row sum)
+fn norm_inf<F: Float + core::fmt::Debug>(m: &[Vec<F>]) -> F {
+  m.iter().map(|r| r.iter().fold(<F>::from(0).unwrap(), |s, &a| s + a.abs()))
+    .fold(<F>::from(0).unwrap(), |s, a| s.max(a))
+}
+
+/// a + b
+fn add<F: Float + core::fmt::Debug>(a: &[Vec<F>], b: &[Vec<F>]) ->
+  Vec<Vec<F>> {
+  a.iter().zip(b.iter()).map(|(p, q)|
+    p.iter().zip(q.iter()).map(|(&x, &y)| x + y).collect()).collect()
+}
+
+/// a - b
+fn sub<F: Float + core::fmt::Debug>(a: &[Vec<F>], b: &[Vec<F>]) ->
+  Vec<Vec<F>> {
+  a.iter().zip(b.iter()).map(|(p, q)|
+    p.iter().zip(q.iter()).map(|(&x, &y)| x - y).collect()).collect()
+}
+
+/// a * s
+fn scale<F: Float + core::fmt::Debug>(a: &[Vec<F>], s: F) -> Vec<Vec<F>> {
+  a.iter().map(|p| p.iter().map(|&x| x * s).collect()).collect()
+}
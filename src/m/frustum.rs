@@ -0,0 +1,92 @@
+//! frustum planes and culling
+//!
+
+use num::Float;
+
+use crate::v::{v3::Vector3, v4::Vector4};
+use crate::m::{Conv, mn::transposed, m4::Matrix4};
+use crate::m::cam::{Proj, Depth};
+
+/// Cull (result of the frustum tests)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cull {
+  /// completely inside
+  Inside,
+  /// completely outside
+  Outside,
+  /// crossing at least one plane
+  Intersect,
+}
+
+/// Frustum (6 planes \[a, b, c, d\], inside when a x + b y + c z + d >= 0)
+/// - order: left right bottom top near far
+/// - (a, b, c) is normalized (except a degenerated far plane of infinite far)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frustum<F> {
+  /// planes
+  pub planes: [Vector4<F>; 6],
+}
+
+/// Frustum
+impl<F: Float + core::fmt::Debug> Frustum<F> {
+  /// constructor from view projection (Gribb-Hartmann)
+  /// - planes are in world space (in view space when m is projection only)
+  /// - p: same depth and conv as the projection builders
+  pub fn new(m: &Matrix4<F>, p: Proj) -> Self {
+    let o = <F>::from(0).unwrap();
+    let l = <F>::from(1).unwrap();
+    let m = match p.conv {
+    Conv::Col => *m,
+    Conv::Row => transposed(m)
+    };
+    let add = |a: &Vector4<F>, b: &Vector4<F>, s: F|
+      core::array::from_fn(|i| a[i] + s * b[i]);
+    let near = match p.depth {
+    Depth::ZeroOne => add(&[o; 4], &m[2], l), // 0 <= z
+    Depth::NegOneOne => add(&m[3], &m[2], l) // -w <= z
+    };
+    let planes = [
+      add(&m[3], &m[0], l), add(&m[3], &m[0], -l),
+      add(&m[3], &m[1], l), add(&m[3], &m[1], -l),
+      near, add(&m[3], &m[2], -l)];
+    Frustum{planes: planes.map(|q| {
+      let n = (q[0] * q[0] + q[1] * q[1] + q[2] * q[2]).sqrt();
+      if n > o { q.map(|x| x / n) } else { q }
+    })}
+  }
+  /// signed distance from plane i to v (positive inside)
+  pub fn distance(&self, i: usize, v: &Vector3<F>) -> F {
+    let q = &self.planes[i];
+    q[0] * v[0] + q[1] * v[1] + q[2] * v[2] + q[3]
+  }
+  /// point v is inside (on the boundary is inside)
+  pub fn contains(&self, v: &Vector3<F>) -> bool {
+    (0..6).all(|i| self.distance(i, v) >= <F>::from(0).unwrap())
+  }
+  /// sphere of center c and radius r
+  pub fn sphere(&self, c: &Vector3<F>, r: F) -> Cull {
+    let mut res = Cull::Inside;
+    for i in 0..6 {
+      let d = self.distance(i, c);
+      if d < -r { return Cull::Outside; }
+      if d < r { res = Cull::Intersect; }
+    }
+    res
+  }
+  /// axis aligned box from lo to hi
+  /// - conservative (a large box near a corner may be Intersect)
+  pub fn aabb(&self, lo: &Vector3<F>, hi: &Vector3<F>) -> Cull {
+    let o = <F>::from(0).unwrap();
+    let mut res = Cull::Inside;
+    for i in 0..6 {
+      let q = &self.planes[i];
+      let far: Vector3<F> = core::array::from_fn(|k| // farthest inside
+        if q[k] >= o { hi[k] } else { lo[k] });
+      let near: Vector3<F> = core::array::from_fn(|k|
+        if q[k] >= o { lo[k] } else { hi[k] });
+      if self.distance(i, &far) < o { return Cull::Outside; }
+      if self.distance(i, &near) < o { res = Cull::Intersect; }
+    }
+    res
+  }
+}
@@ -0,0 +1,114 @@
+//! LU decomposition
+//!
+
+use num::Float;
+
+use alloc::vec::Vec;
+
+use crate::e::Result;
+use crate::m::check_square;
+
+/// LU decomposition with partial pivoting (P m = L U)
+#[derive(Debug, Clone)]
+pub struct LU<F: Float + core::fmt::Debug> {
+  /// L (unit lower, diagonal omitted) and U packed in one matrix
+  pub lu: Vec<Vec<F>>,
+  /// row permutation (row i of P m is row piv\[i\] of m)
+  pub piv: Vec<usize>,
+  /// sign of the permutation (1 or -1)
+  pub sign: F,
+}
+
+/// LU
+impl<F: Float + core::fmt::Debug> LU<F> {
+  /// constructor (decompose square m)
+  /// - a zero pivot column is left as is (det = 0)
+  /// - error when m is not square
+  pub fn new(m: &[Vec<F>]) -> Result<Self> {
+    check_square(m)?;
+    let n = m.len();
+    let o = <F>::from(0).unwrap();
+    let mut lu = m.to_vec();
+    let mut piv = (0..n).collect::<Vec<_>>();
+    let mut sign = <F>::from(1).unwrap();
+    for k in 0..n {
+      let p = (k..n).fold(k, |p, i|
+        if lu[i][k].abs() > lu[p][k].abs() { i } else { p });
+      if p != k {
+        lu.swap(p, k);
+        piv.swap(p, k);
+        sign = -sign;
+      }
+      let d = lu[k][k];
+      if d == o { continue; }
+      for i in k+1..n {
+        let f = lu[i][k] / d;
+        lu[i][k] = f;
+        #[allow(clippy::needless_range_loop)] // rows i and k of lu
+        for j in k+1..n { lu[i][j] = lu[i][j] - f * lu[k][j]; }
+      }
+    }
+    Ok(LU{lu, piv, sign})
+  }
+  /// size
+  pub fn n(&self) -> usize {
+    self.lu.len()
+  }
+  /// check singular (some pivot is 0 within prec p)
+  pub fn is_singular(&self, p: F) -> bool {
+    let o = <F>::from(0).unwrap();
+    (0..self.n()).any(|i| crate::prec_eq_f(self.lu[i][i], p, o))
+  }
+  /// det
+  pub fn det(&self) -> F {
+    (0..self.n()).fold(self.sign, |d, i| d * self.lu[i][i])
+  }
+  /// L (unit lower triangular)
+  pub fn l(&self) -> Vec<Vec<F>> {
+    let o = <F>::from(0).unwrap();
+    let l = <F>::from(1).unwrap();
+    self.lu.iter().enumerate().map(|(j, r)|
+      r.iter().enumerate().map(|(i, &c)|
+        if i < j { c } else if i == j { l } else { o }
+      ).collect::<Vec<_>>()
+    ).collect::<Vec<_>>()
+  }
+  /// U (upper triangular)
+  pub fn u(&self) -> Vec<Vec<F>> {
+    let o = <F>::from(0).unwrap();
+    self.lu.iter().enumerate().map(|(j, r)|
+      r.iter().enumerate().map(|(i, &c)|
+        if i >= j { c } else { o }
+      ).collect::<Vec<_>>()
+    ).collect::<Vec<_>>()
+  }
+  /// solve m x = b
+  pub fn solve(&self, b: &[F]) -> Vec<F> {
+    let n = self.n();
+    let mut x = self.piv.iter().map(|&p| b[p]).collect::<Vec<_>>();
+    for j in 0..n { // forward L y = P b
+      for i in 0..j { x[j] = x[j] - self.lu[j][i] * x[i]; }
+    }
+    for j in (0..n).rev() { // backward U x = y
+      for i in j+1..n { x[j] = x[j] - self.lu[j][i] * x[i]; }
+      x[j] = x[j] / self.lu[j][j];
+    }
+    x
+  }
+  /// solve m x = b for each column b of bm (reuse decomposition)
+  pub fn solve_m(&self, bm: &[Vec<F>]) -> Vec<Vec<F>> {
+    let t = crate::m::transpose_unchecked(bm);
+    crate::m::transpose_unchecked(&t.iter().map(|b|
+      self.solve(b)
+    ).collect::<Vec<_>>())
+  }
+  /// inv
+  pub fn inv(&self) -> Vec<Vec<F>> {
+    let o = <F>::from(0).unwrap();
+    let l = <F>::from(1).unwrap();
+    let n = self.n();
+    self.solve_m(&(0..n).map(|j|
+      (0..n).map(|i| if i == j { l } else { o }).collect()
+    ).collect::<Vec<_>>())
+  }
+}
@@ -3,107 +3,74 @@
 
 use num::Float;
 
-use crate::v::{TVector, v4::Vector4};
-use crate::m::TMatrix;
+use crate::v::{v3::Vector3, v4::Vector4};
+use crate::m::{Conv, mn::Matrix, m3::Matrix3, affine::affine};
 use crate::q::TQuaternion;
 
-/// TM4
-pub trait TM4<F: Float + std::fmt::Debug> {
+/// TM4 (methods defined only for Matrix4)
+pub trait TM4<F: Float + core::fmt::Debug> {
+  /// constructor col major from v4 (move)
+  fn colmajor4(m: [Vector4<F>; 4]) -> Self;
+  /// constructor row major from v4 (move)
+  fn rowmajor4(m: [Vector4<F>; 4]) -> Self;
+  /// like as slice v4
+  fn mev4(&self) -> &[Vector4<F>];
+  /// row to v4
+  fn rowv4(&self, j: usize) -> Vector4<F>;
+  /// col to v4
+  fn colv4(&self, i: usize) -> Vector4<F>;
   /// from Quaternion (qp = Q4x4 p4)
+  /// - p4 is \[x, y, z, w\] (w last) while Quaternion is \[w, x, y, z\]
   fn from_q_left(q: &impl TQuaternion<F>) -> Self;
   /// from Quaternion (qp = P4x4 q4)
+  /// - q4 is \[x, y, z, w\] (w last) while Quaternion is \[w, x, y, z\]
   fn from_p_right(p: &impl TQuaternion<F>) -> Self;
   /// from Quaternion rot (qrp = P4x4 Q4x4 r4)
+  /// - column vector form on \[x, y, z, w\] (same as rot_q with Conv::Col)
   fn from_rot(q: &impl TQuaternion<F>) -> Self;
+  /// embed linear part m (the rest is identity)
+  /// - m is in column vector form (Conv::Row stores the transpose)
+  fn from_m3(m: &Matrix3<F>, c: Conv) -> Self;
+  /// embed linear part m with translation t (same as affine::affine)
+  /// - m is in column vector form (Conv::Row stores the transpose)
+  fn from_m3_t(m: &Matrix3<F>, t: &Vector3<F>, c: Conv) -> Self;
+  /// linear part in column vector form (inverse of from_m3)
+  /// - upper left 3x3 for Conv::Col, its transpose for Conv::Row
+  fn to_m3(&self, c: Conv) -> Matrix3<F>;
+  /// translation (column 3 for Conv::Col, row 3 for Conv::Row)
+  fn translation(&self, c: Conv) -> Vector3<F>;
+  /// perspective (row 3 for Conv::Col, column 3 for Conv::Row)
+  fn persp(&self, c: Conv) -> Vector4<F>;
 }
 
 /// Matrix4
-pub type Matrix4<F> = [[F; 4]; 4];
+pub type Matrix4<F> = Matrix<F, 4, 4>;
 
-/// TMatrix for Matrix4
-impl<F: Float + std::fmt::Debug + std::iter::Sum> TMatrix<F> for Matrix4<F> {
+/// TM4 for Matrix4
+impl<F: Float + core::fmt::Debug> TM4<F> for Matrix4<F> {
   /// constructor col major from v4 (move)
-  fn colmajor4(m: Vec<Vector4<F>>) -> Self where Self: Sized {
-    (0..4).into_iter().map(|i|
-      (0..4).into_iter().map(|j|
-        m[j][i]
-      ).collect::<Vec<_>>().try_into().unwrap()
-    ).collect::<Vec<_>>().try_into().unwrap()
+  fn colmajor4(m: [Vector4<F>; 4]) -> Self {
+    core::array::from_fn(|j| core::array::from_fn(|i| m[i][j]))
   }
   /// constructor row major from v4 (move)
-  fn rowmajor4(m: Vec<Vector4<F>>) -> Self where Self: Sized {
-    (0..4).into_iter().map(|j|
-      m[j]
-    ).collect::<Vec<_>>().try_into().unwrap()
-  }
-  /// constructor col major
-  fn col_major(m: &Vec<Vec<F>>) -> Self {
-    (0..4).into_iter().map(|i|
-      (0..4).into_iter().map(|j|
-        m[j][i]
-      ).collect::<Vec<_>>().try_into().unwrap()
-    ).collect::<Vec<_>>().try_into().unwrap()
-  }
-  /// constructor row major
-  fn row_major(m: &Vec<Vec<F>>) -> Self {
-    (0..4).into_iter().map(|j|
-      (0..4).into_iter().map(|i|
-        m[j][i]
-      ).collect::<Vec<_>>().try_into().unwrap()
-    ).collect::<Vec<_>>().try_into().unwrap()
-  }
-  /// constructor row major
-  fn new(m: &Vec<Vec<F>>) -> Self {
-    Self::row_major(m)
-  }
-  /// constructor
-  fn identity() -> Self {
-    let o = <F>::from(0).unwrap();
-    let l = <F>::from(1).unwrap();
-    Self::new(&vec![
-      vec![l, o, o, o],
-      vec![o, l, o, o],
-      vec![o, o, l, o],
-      vec![o, o, o, l]])
-  }
-  /// check equal with precision
-  fn prec_eq(&self, e: F, m: &impl TMatrix<F>) -> bool {
-    let m = m.mev4();
-    for j in 0..4 {
-      for i in 0..4 {
-        if (self[j][i] - m[j][i]).abs() >= e { return false; }
-      }
-    }
-    true
+  fn rowmajor4(m: [Vector4<F>; 4]) -> Self {
+    m
   }
   /// like as slice v4
   fn mev4(&self) -> &[Vector4<F>] {
     self
   }
-  /// m dot self
-  fn dot_m(&self, m: &impl TMatrix<F>) -> Self {
-    Matrix4::<F>::colmajor4((0..4).into_iter().map(|i| {
-      self.colv4(i).dot_mv(m) // m dot self.col
-    }).collect())
-  }
   /// row to v4
   fn rowv4(&self, j: usize) -> Vector4<F> {
     self[j]
-//    Vector4::<F>::new(&(0..4).into_iter().map(|i| self[j][i]).collect())
   }
   /// col to v4
   fn colv4(&self, i: usize) -> Vector4<F> {
-    Vector4::<F>::new(&(0..4).into_iter().map(|j| self[j][i]).collect())
+    core::array::from_fn(|j| self[j][i])
   }
-  /// to_vec
-  fn to_vec(&self) -> Vec<Vec<F>> {
-    self.iter().map(|r| r.to_vec()).collect::<Vec<_>>()
-  }
-}
-
-/// TM4 for Matrix4
-impl<F: Float + std::fmt::Debug> TM4<F> for Matrix4<F> {
   /// from Quaternion (qp = Q4x4 p4)
+  /// - p4 is \[x, y, z, w\] (w last) while Quaternion is \[w, x, y, z\]
+  /// - the comment is the same matrix for p4 \[w, x, y, z\]
   fn from_q_left(q: &impl TQuaternion<F>) -> Self {
     let q = q.me();
     [
@@ -120,6 +87,8 @@ impl<F: Float + std::fmt::Debug> TM4<F> for Matrix4<F> {
     ]
   }
   /// from Quaternion (qp = P4x4 q4)
+  /// - q4 is \[x, y, z, w\] (w last) while Quaternion is \[w, x, y, z\]
+  /// - the comment is the same matrix for q4 \[w, x, y, z\]
   fn from_p_right(p: &impl TQuaternion<F>) -> Self {
     let p = p.me();
     [
@@ -136,7 +105,40 @@ impl<F: Float + std::fmt::Debug> TM4<F> for Matrix4<F> {
     ]
   }
   /// from Quaternion rot (qrp = P4x4 Q4x4 r4)
+  /// - column vector form on \[x, y, z, w\] (same as rot_q with Conv::Col)
   fn from_rot(q: &impl TQuaternion<F>) -> Self {
     q.to_m4_rot()
   }
+  /// embed linear part m (the rest is identity)
+  /// - m is in column vector form (Conv::Row stores the transpose)
+  fn from_m3(m: &Matrix3<F>, c: Conv) -> Self {
+    affine(m, &[<F>::from(0).unwrap(); 3], c)
+  }
+  /// embed linear part m with translation t (same as affine::affine)
+  /// - m is in column vector form (Conv::Row stores the transpose)
+  fn from_m3_t(m: &Matrix3<F>, t: &Vector3<F>, c: Conv) -> Self {
+    affine(m, t, c)
+  }
+  /// linear part in column vector form (inverse of from_m3)
+  /// - upper left 3x3 for Conv::Col, its transpose for Conv::Row
+  fn to_m3(&self, c: Conv) -> Matrix3<F> {
+    match c {
+    Conv::Col => core::array::from_fn(|j| core::array::from_fn(|i| self[j][i])),
+    Conv::Row => core::array::from_fn(|j| core::array::from_fn(|i| self[i][j]))
+    }
+  }
+  /// translation (column 3 for Conv::Col, row 3 for Conv::Row)
+  fn translation(&self, c: Conv) -> Vector3<F> {
+    match c {
+    Conv::Col => core::array::from_fn(|j| self[j][3]),
+    Conv::Row => core::array::from_fn(|i| self[3][i])
+    }
+  }
+  /// perspective (row 3 for Conv::Col, column 3 for Conv::Row)
+  fn persp(&self, c: Conv) -> Vector4<F> {
+    match c {
+    Conv::Col => self.rowv4(3),
+    Conv::Row => self.colv4(3)
+    }
+  }
 }
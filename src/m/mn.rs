@@ -0,0 +1,273 @@
+//! matrixRxC
+//!
+
+use num::Float;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::e::{Error, Result};
+use crate::v::vn::Vector;
+use crate::m::TMatrix;
+
+/// Matrix (const generic R rows x C cols)
+pub type Matrix<F, const R: usize, const C: usize> = [[F; C]; R];
+
+/// TMatrix for Matrix (square N x N)
+/// - construction, dot, transpose, det and inv work on the stack
+impl<F: Float + core::fmt::Debug + core::iter::Sum, const N: usize> TMatrix<F>
+  for Matrix<F, N, N> {
+  /// constructor col major
+  #[cfg(feature = "alloc")]
+  fn col_major(m: &Vec<Vec<F>>) -> Self {
+    core::array::from_fn(|j| core::array::from_fn(|i| m[i][j]))
+  }
+  /// constructor row major
+  #[cfg(feature = "alloc")]
+  fn row_major(m: &Vec<Vec<F>>) -> Self {
+    core::array::from_fn(|j| core::array::from_fn(|i| m[j][i]))
+  }
+  /// constructor row major
+  #[cfg(feature = "alloc")]
+  fn new(m: &Vec<Vec<F>>) -> Self {
+    Self::row_major(m)
+  }
+  /// constructor row major
+  #[cfg(feature = "alloc")]
+  fn try_new(m: &Vec<Vec<F>>) -> Result<Self> {
+    from_vec(m)
+  }
+  /// constructor row major from flat slice
+  fn try_from_slice(m: &[F]) -> Result<Self> {
+    try_from_slice(m)
+  }
+  /// constructor row major from flat iterator
+  fn try_from_iter(it: impl IntoIterator<Item = F>) -> Result<Self> {
+    try_from_iter(it)
+  }
+  /// constructor
+  fn identity() -> Self {
+    let o = <F>::from(0).unwrap();
+    let l = <F>::from(1).unwrap();
+    core::array::from_fn(|j| core::array::from_fn(|i| if i == j { l } else { o }))
+  }
+  /// check equal with precision
+  fn prec_eq(&self, e: F, m: &Self) -> bool {
+    for j in 0..N {
+      for i in 0..N {
+        if (self[j][i] - m[j][i]).abs() >= e { return false; }
+      }
+    }
+    true
+  }
+  /// m dot self
+  /// - for Conv::Col self is applied first (see xform::Xform::then)
+  fn dot_m(&self, m: &Self) -> Self {
+    mul(m, self)
+  }
+  /// row as slice
+  fn row(&self, j: usize) -> &[F] {
+    &self[j]
+  }
+  /// to_vec
+  #[cfg(feature = "alloc")]
+  fn to_vec(&self) -> Vec<Vec<F>> {
+    self.iter().map(|r| r.to_vec()).collect::<Vec<_>>()
+  }
+  /// transpose
+  fn transpose(&self) -> Self where Self: Sized {
+    transposed(self)
+  }
+  /// det
+  /// - closed form for 1x1 2x2 3x3 4x4 (others Gauss-Jordan on the stack)
+  fn det(&self) -> F {
+    match N {
+    1 => self[0][0],
+    2 => self[0][0] * self[1][1] - self[0][1] * self[1][0],
+    3 => det3(self),
+    4 => adj4(self).0,
+    _ => gauss_jordan(self).0
+    }
+  }
+  /// inv
+  /// - closed form for 1x1 2x2 3x3 4x4 (others Gauss-Jordan on the stack)
+  /// - p: prec (assume det = 0)
+  fn inv(&self, p: F) -> Option<Self> where Self: Sized {
+    let o = <F>::from(0).unwrap();
+    let (d, mut r) = match N {
+    1 => (self[0][0], Self::identity()),
+    2 => {
+      let mut r = *self;
+      (r[0][0], r[0][1], r[1][0], r[1][1]) =
+        (self[1][1], -self[0][1], -self[1][0], self[0][0]);
+      (self.det(), r)
+    },
+    3 => adj3(self),
+    4 => adj4(self),
+    _ => {
+      let (d, r) = gauss_jordan(self);
+      return if crate::prec_eq_f(d, p, o) { None } else { Some(r) };
+    }
+    };
+    if crate::prec_eq_f(d, p, o) { return None; }
+    for row in r.iter_mut() {
+      for c in row.iter_mut() { *c = *c / d; }
+    }
+    Some(r)
+  }
+}
+
+/// (det, inverse) by Gauss-Jordan elimination with partial pivoting
+/// - inverse is meaningless when det = 0
+fn gauss_jordan<F: Float + core::fmt::Debug + core::iter::Sum, const N: usize>(
+  m: &Matrix<F, N, N>) -> (F, Matrix<F, N, N>) {
+  let o = <F>::from(0).unwrap();
+  let l = <F>::from(1).unwrap();
+  let mut a = *m;
+  let mut r = <Matrix<F, N, N> as TMatrix<F>>::identity();
+  let mut d = l;
+  for k in 0..N {
+    let p = (k..N).fold(k, |p, j|
+      if a[j][k].abs() > a[p][k].abs() { j } else { p });
+    if a[p][k] == o { return (o, r); }
+    if p != k { a.swap(p, k); r.swap(p, k); d = -d; }
+    let q = a[k][k];
+    d = d * q;
+    for i in 0..N { a[k][i] = a[k][i] / q; r[k][i] = r[k][i] / q; }
+    for j in 0..N {
+      if j == k || a[j][k] == o { continue; }
+      let f = a[j][k];
+      for i in 0..N {
+        a[j][i] = a[j][i] - f * a[k][i];
+        r[j][i] = r[j][i] - f * r[k][i];
+      }
+    }
+  }
+  (d, r)
+}
+
+/// det (N = 3)
+fn det3<F: Float, const N: usize>(m: &Matrix<F, N, N>) -> F {
+  m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+  + m[0][1] * (m[1][2] * m[2][0] - m[1][0] * m[2][2])
+  + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+}
+
+/// (det, adjugate) (N = 3)
+fn adj3<F: Float, const N: usize>(m: &Matrix<F, N, N>) -> (F, Matrix<F, N, N>) {
+  let mut r = *m;
+  r[0][0] = m[1][1] * m[2][2] - m[1][2] * m[2][1];
+  r[0][1] = m[0][2] * m[2][1] - m[0][1] * m[2][2];
+  r[0][2] = m[0][1] * m[1][2] - m[0][2] * m[1][1];
+  r[1][0] = m[1][2] * m[2][0] - m[1][0] * m[2][2];
+  r[1][1] = m[0][0] * m[2][2] - m[0][2] * m[2][0];
+  r[1][2] = m[0][2] * m[1][0] - m[0][0] * m[1][2];
+  r[2][0] = m[1][0] * m[2][1] - m[1][1] * m[2][0];
+  r[2][1] = m[0][1] * m[2][0] - m[0][0] * m[2][1];
+  r[2][2] = m[0][0] * m[1][1] - m[0][1] * m[1][0];
+  (m[0][0] * r[0][0] + m[0][1] * r[1][0] + m[0][2] * r[2][0], r)
+}
+
+/// (det, adjugate) (N = 4, expansion by 2x2 minors)
+fn adj4<F: Float, const N: usize>(m: &Matrix<F, N, N>) -> (F, Matrix<F, N, N>) {
+  let s0 = m[0][0] * m[1][1] - m[1][0] * m[0][1];
+  let s1 = m[0][0] * m[1][2] - m[1][0] * m[0][2];
+  let s2 = m[0][0] * m[1][3] - m[1][0] * m[0][3];
+  let s3 = m[0][1] * m[1][2] - m[1][1] * m[0][2];
+  let s4 = m[0][1] * m[1][3] - m[1][1] * m[0][3];
+  let s5 = m[0][2] * m[1][3] - m[1][2] * m[0][3];
+  let c5 = m[2][2] * m[3][3] - m[3][2] * m[2][3];
+  let c4 = m[2][1] * m[3][3] - m[3][1] * m[2][3];
+  let c3 = m[2][1] * m[3][2] - m[3][1] * m[2][2];
+  let c2 = m[2][0] * m[3][3] - m[3][0] * m[2][3];
+  let c1 = m[2][0] * m[3][2] - m[3][0] * m[2][2];
+  let c0 = m[2][0] * m[3][1] - m[3][0] * m[2][1];
+  let d = s0 * c5 - s1 * c4 + s2 * c3 + s3 * c2 - s4 * c1 + s5 * c0;
+  let mut r = *m;
+  r[0][0] = m[1][1] * c5 - m[1][2] * c4 + m[1][3] * c3;
+  r[0][1] = -m[0][1] * c5 + m[0][2] * c4 - m[0][3] * c3;
+  r[0][2] = m[3][1] * s5 - m[3][2] * s4 + m[3][3] * s3;
+  r[0][3] = -m[2][1] * s5 + m[2][2] * s4 - m[2][3] * s3;
+  r[1][0] = -m[1][0] * c5 + m[1][2] * c2 - m[1][3] * c1;
+  r[1][1] = m[0][0] * c5 - m[0][2] * c2 + m[0][3] * c1;
+  r[1][2] = -m[3][0] * s5 + m[3][2] * s2 - m[3][3] * s1;
+  r[1][3] = m[2][0] * s5 - m[2][2] * s2 + m[2][3] * s1;
+  r[2][0] = m[1][0] * c4 - m[1][1] * c2 + m[1][3] * c0;
+  r[2][1] = -m[0][0] * c4 + m[0][1] * c2 - m[0][3] * c0;
+  r[2][2] = m[3][0] * s4 - m[3][1] * s2 + m[3][3] * s0;
+  r[2][3] = -m[2][0] * s4 + m[2][1] * s2 - m[2][3] * s0;
+  r[3][0] = -m[1][0] * c3 + m[1][1] * c1 - m[1][2] * c0;
+  r[3][1] = m[0][0] * c3 - m[0][1] * c1 + m[0][2] * c0;
+  r[3][2] = -m[3][0] * s3 + m[3][1] * s1 - m[3][2] * s0;
+  r[3][3] = m[2][0] * s3 - m[2][1] * s1 + m[2][2] * s0;
+  (d, r)
+}
+
+/// a dot b (R x K dot K x C = R x C, any size checked at compile time)
+pub fn mul<F: Float + core::fmt::Debug + core::iter::Sum,
+  const R: usize, const K: usize, const C: usize>(
+  a: &Matrix<F, R, K>, b: &Matrix<F, K, C>) -> Matrix<F, R, C> {
+  core::array::from_fn(|j| core::array::from_fn(|i|
+    (0..K).map(|k| a[j][k] * b[k][i]).sum::<F>()))
+}
+
+/// a dot v (R x C dot C = R)
+pub fn mul_mv<F: Float + core::fmt::Debug + core::iter::Sum,
+  const R: usize, const C: usize>(
+  a: &Matrix<F, R, C>, v: &Vector<F, C>) -> Vector<F, R> {
+  core::array::from_fn(|j|
+    (0..C).map(|i| a[j][i] * v[i]).sum::<F>())
+}
+
+/// transpose (R x C to C x R)
+pub fn transposed<F: Float + core::fmt::Debug,
+  const R: usize, const C: usize>(m: &Matrix<F, R, C>) -> Matrix<F, C, R> {
+  core::array::from_fn(|j| core::array::from_fn(|i| m[i][j]))
+}
+
+/// from rows (R x C)
+/// - error when the number of rows or cols is not R or C
+#[cfg(feature = "alloc")]
+pub fn from_vec<F: Float + core::fmt::Debug,
+  const R: usize, const C: usize>(m: &[Vec<F>]) -> Result<Matrix<F, R, C>> {
+  if m.len() != R {
+    return Err(Error::DimensionMismatch{expected: R, actual: m.len()});
+  }
+  if let Some(r) = m.iter().find(|r| r.len() != C) {
+    return Err(Error::DimensionMismatch{expected: C, actual: r.len()});
+  }
+  Ok(core::array::from_fn(|j| core::array::from_fn(|i| m[j][i])))
+}
+
+/// from flat slice (R x C, row major)
+/// - error when the length is not R * C
+pub fn try_from_slice<F: Float + core::fmt::Debug,
+  const R: usize, const C: usize>(m: &[F]) -> Result<Matrix<F, R, C>> {
+  if m.len() != R * C {
+    return Err(Error::DimensionMismatch{expected: R * C, actual: m.len()});
+  }
+  Ok(core::array::from_fn(|j| core::array::from_fn(|i| m[j * C + i])))
+}
+
+/// from flat iterator (R x C, row major)
+/// - error when the number of items is not R * C (all items are counted)
+pub fn try_from_iter<F: Float + core::fmt::Debug,
+  const R: usize, const C: usize>(it: impl IntoIterator<Item = F>) ->
+  Result<Matrix<F, R, C>> {
+  let mut m = [[<F>::from(0).unwrap(); C]; R];
+  let n = it.into_iter().fold(0, |n, x| {
+    if n < R * C { m[n / C][n % C] = x; }
+    n + 1
+  });
+  if n != R * C {
+    return Err(Error::DimensionMismatch{expected: R * C, actual: n});
+  }
+  Ok(m)
+}
+
+/// to rows (R x C)
+#[cfg(feature = "alloc")]
+pub fn to_vec<F: Float + core::fmt::Debug,
+  const R: usize, const C: usize>(m: &Matrix<F, R, C>) -> Vec<Vec<F>> {
+  m.iter().map(|r| r.to_vec()).collect::<Vec<_>>()
+}
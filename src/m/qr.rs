@@ -0,0 +1,133 @@
+//! QR decomposition
+//!
+
+use num::Float;
+
+use alloc::{vec, vec::Vec};
+
+use crate::e::{Error, Result};
+use crate::m::check_rect;
+
+/// QR decomposition by Householder reflections (m P = Q R)
+/// - m: rows x cols (rectangular)
+/// - P: column permutation (identity when not pivoted)
+#[derive(Debug, Clone)]
+pub struct QR<F: Float + core::fmt::Debug> {
+  /// Householder unit vectors (H_k = I - 2 v_k v_k^T, zero above k)
+  pub v: Vec<Vec<F>>,
+  /// R (rows x cols, zero below diagonal)
+  pub r: Vec<Vec<F>>,
+  /// column permutation (column i of m P is column piv\[i\] of m)
+  pub piv: Vec<usize>,
+}
+
+/// QR
+impl<F: Float + core::fmt::Debug> QR<F> {
+  /// constructor (decompose m = Q R)
+  /// - error when m is empty or ragged
+  pub fn new(m: &[Vec<F>]) -> Result<Self> {
+    check_rect(m)?;
+    Ok(Self::decompose(m, false))
+  }
+  /// constructor (decompose m P = Q R with column pivoting)
+  /// - |diagonal of R| is non increasing (rank revealing)
+  /// - error when m is empty or ragged
+  pub fn new_pivot(m: &[Vec<F>]) -> Result<Self> {
+    check_rect(m)?;
+    Ok(Self::decompose(m, true))
+  }
+  /// decompose
+  fn decompose(m: &[Vec<F>], pivot: bool) -> Self {
+    let o = <F>::from(0).unwrap();
+    let (rows, cols) = (m.len(), m[0].len());
+    let mut r = m.to_vec();
+    let mut piv = (0..cols).collect::<Vec<_>>();
+    let mut v = Vec::<Vec<F>>::new();
+    for k in 0..rows.min(cols) {
+      if pivot {
+        let nrm = |i: usize| (k..rows).map(|j|
+          r[j][i] * r[j][i]).fold(o, |s, a| s + a);
+        let p = (k..cols).fold(k, |p, i|
+          if nrm(i) > nrm(p) { i } else { p });
+        if p != k {
+          for row in r.iter_mut() { row.swap(p, k); }
+          piv.swap(p, k);
+        }
+      }
+      let mut h = (0..rows).map(|j|
+        if j < k { o } else { r[j][k] }).collect::<Vec<_>>();
+      let a = h.iter().fold(o, |s, &x| s + x * x).sqrt();
+      h[k] = h[k] + if h[k] < o { -a } else { a };
+      let d = h.iter().fold(o, |s, &x| s + x * x).sqrt();
+      if d == o { v.push(h); continue; } // column is already zero
+      for x in h.iter_mut() { *x = *x / d; }
+      reflect(&h, &mut r, k);
+      for row in r.iter_mut().skip(k + 1) { row[k] = o; }
+      v.push(h);
+    }
+    QR{v, r, piv}
+  }
+  /// rows
+  pub fn rows(&self) -> usize {
+    self.r.len()
+  }
+  /// cols
+  pub fn cols(&self) -> usize {
+    self.r[0].len()
+  }
+  /// apply Q^T to b
+  pub fn qt(&self, b: &[F]) -> Vec<F> {
+    let mut c = b.iter().map(|&x| vec![x]).collect::<Vec<_>>();
+    for (k, h) in self.v.iter().enumerate() { reflect(h, &mut c, k); }
+    c.into_iter().map(|x| x[0]).collect()
+  }
+  /// thin Q (rows x min(rows, cols), orthonormal columns)
+  pub fn q(&self) -> Vec<Vec<F>> {
+    let o = <F>::from(0).unwrap();
+    let l = <F>::from(1).unwrap();
+    let k = self.rows().min(self.cols());
+    let mut q = (0..self.rows()).map(|j|
+      (0..k).map(|i| if i == j { l } else { o }).collect()
+    ).collect::<Vec<Vec<F>>>();
+    for (i, h) in self.v.iter().enumerate().rev() { reflect(h, &mut q, i); }
+    q
+  }
+  /// numerical rank
+  /// - rtol: |r_ii| <= rtol * max |r_jj| is treated as 0
+  /// - use new_pivot (without pivoting the estimate is not reliable)
+  pub fn rank(&self, rtol: F) -> usize {
+    let o = <F>::from(0).unwrap();
+    let k = self.rows().min(self.cols());
+    let mx = (0..k).fold(o, |s, i| s.max(self.r[i][i].abs()));
+    (0..k).filter(|&i| self.r[i][i].abs() > rtol * mx).count()
+  }
+  /// least squares solution of min |m x - b|
+  /// - rank deficient columns (rtol) are set to 0 (basic solution)
+  pub fn solve(&self, b: &[F], rtol: F) -> Result<Vec<F>> {
+    if b.len() != self.rows() {
+      return Err(Error::DimensionMismatch{expected: self.rows(), actual: b.len()});
+    }
+    let o = <F>::from(0).unwrap();
+    let n = self.rank(rtol);
+    let c = self.qt(b);
+    let mut y = vec![o; self.cols()];
+    for j in (0..n).rev() {
+      let s = (j+1..n).fold(c[j], |s, i| s - self.r[j][i] * y[i]);
+      y[j] = s / self.r[j][j];
+    }
+    let mut x = vec![o; self.cols()];
+    for (i, &p) in self.piv.iter().enumerate() { x[p] = y[i]; }
+    Ok(x)
+  }
+}
+
+/// apply H = I - 2 h h^T to rows k.. of a (h is zero above k)
+fn reflect<F: Float + core::fmt::Debug>(h: &[F], a: &mut [Vec<F>],
+  k: usize) {
+  let o = <F>::from(0).unwrap();
+  let t = <F>::from(2).unwrap();
+  for i in 0..a[0].len() {
+    let s = (k..a.len()).fold(o, |s, j| s + h[j] * a[j][i]);
+    for j in k..a.len() { a[j][i] = a[j][i] - t * h[j] * s; }
+  }
+}
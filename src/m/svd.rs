@@ -0,0 +1,196 @@
+//! singular value decomposition
+//!
+
+use num::Float;
+
+use alloc::{vec, vec::Vec};
+
+use crate::e::Result;
+use crate::m::{Polar, check_rect, check_square, lu::LU};
+use crate::m::{transpose_unchecked, mul_unchecked};
+
+/// singular value decomposition by one sided Jacobi rotations
+/// - m = U diag(s) V^T (m: rows x cols, k = min(rows, cols))
+#[derive(Debug, Clone)]
+pub struct SVD<F: Float + core::fmt::Debug> {
+  /// U (rows x k, orthonormal columns)
+  pub u: Vec<Vec<F>>,
+  /// singular values (descending order)
+  pub s: Vec<F>,
+  /// V^T (k x cols, orthonormal rows, largest component of each is positive)
+  pub vt: Vec<Vec<F>>,
+}
+
+/// SVD
+impl<F: Float + core::fmt::Debug> SVD<F> {
+  /// constructor (decompose m)
+  /// - error when m is empty or ragged
+  pub fn new(m: &[Vec<F>]) -> Result<Self> {
+    let (rows, cols) = check_rect(m)?;
+    if rows < cols { // m^T = V diag(s) U^T
+      let t = Self::decompose(&transpose_unchecked(m));
+      return Ok(SVD{u: transpose_unchecked(&t.vt), s: t.s,
+        vt: transpose_unchecked(&t.u)}.positive_vt());
+    }
+    Ok(Self::decompose(m))
+  }
+  /// constructor (decompose square m with det(U) = det(V) = 1)
+  /// - the last singular value takes the sign of det(m)
+  /// - the last row of V^T may be negated (largest component is not kept)
+  /// - error when m is not square
+  pub fn new_rot(m: &[Vec<F>]) -> Result<Self> {
+    check_square(m)?;
+    let mut d = Self::new(m)?;
+    let n = d.s.len();
+    let o = <F>::from(0).unwrap();
+    if LU::new(&d.u)?.det() < o {
+      for r in d.u.iter_mut() { r[n - 1] = -r[n - 1]; }
+      d.s[n - 1] = -d.s[n - 1];
+    }
+    if LU::new(&d.vt)?.det() < o {
+      for c in d.vt[n - 1].iter_mut() { *c = -*c; }
+      d.s[n - 1] = -d.s[n - 1];
+    }
+    Ok(d)
+  }
+  /// negate pairs (column k of U, row k of V^T) to make V^T rows positive
+  fn positive_vt(mut self) -> Self {
+    let o = <F>::from(0).unwrap();
+    for (k, r) in self.vt.iter_mut().enumerate() {
+      let p = r.iter().fold(o, |p, &x| if x.abs() > p.abs() { x } else { p });
+      if p >= o { continue; }
+      for x in r.iter_mut() { *x = -*x; }
+      for u in self.u.iter_mut() { u[k] = -u[k]; }
+    }
+    self
+  }
+  /// decompose (rows >= cols)
+  fn decompose(m: &[Vec<F>]) -> Self {
+    let (rows, n) = (m.len(), m[0].len());
+    let o = <F>::from(0).unwrap();
+    let l = <F>::from(1).unwrap();
+    let mut u = m.to_vec();
+    let mut v = (0..n).map(|j| (0..n).map(|i|
+      if i == j { l } else { o }).collect::<Vec<_>>()).collect::<Vec<_>>();
+    let eps = <F>::epsilon();
+    for _sweep in 0..64 {
+      let mut rotated = false;
+      for p in 0..n {
+        for q in p+1..n {
+          let (a, b, g) = (0..rows).fold((o, o, o), |(a, b, g), k|
+            (a + u[k][p] * u[k][p], b + u[k][q] * u[k][q], g + u[k][p] * u[k][q]));
+          if g == o || g.abs() <= eps * (a * b).sqrt() { continue; }
+          rotated = true;
+          let zeta = (b - a) / (g + g);
+          let t = l / (zeta.abs() + (zeta * zeta + l).sqrt());
+          let t = if zeta < o { -t } else { t };
+          let c = l / (t * t + l).sqrt();
+          let s = t * c;
+          for w in [&mut u, &mut v] {
+            for r in w.iter_mut() {
+              let (rp, rq) = (r[p], r[q]);
+              r[p] = c * rp - s * rq;
+              r[q] = s * rp + c * rq;
+            }
+          }
+        }
+      }
+      if !rotated { break; }
+    }
+    let sv = (0..n).map(|i|
+      (0..rows).fold(o, |s, k| s + u[k][i] * u[k][i]).sqrt()
+    ).collect::<Vec<_>>();
+    let mut idx = (0..n).collect::<Vec<_>>();
+    idx.sort_by(|&i, &j| crate::total_cmp_f(sv[j], sv[i]));
+    let tol = eps * <F>::from(rows).unwrap() * sv[idx[0]];
+    let mut uc = Vec::<Vec<F>>::new(); // columns of U
+    let mut vc = Vec::<Vec<F>>::new(); // columns of V
+    let mut s = Vec::<F>::new();
+    for &i in idx.iter() {
+      let mut vi = (0..n).map(|k| v[k][i]).collect::<Vec<_>>();
+      let k = (0..n).fold(0, |k, j|
+        if vi[j].abs() > vi[k].abs() { j } else { k });
+      let g = if vi[k] < o { -l } else { l };
+      for x in vi.iter_mut() { *x = *x * g; }
+      let ui = if sv[i] > tol {
+        (0..rows).map(|k| u[k][i] * g / sv[i]).collect()
+      } else {
+        complement(&uc, rows)
+      };
+      uc.push(ui);
+      vc.push(vi);
+      s.push(if sv[i] > tol { sv[i] } else { o });
+    }
+    SVD{u: transpose_unchecked(&uc), s, vt: vc}
+  }
+  /// numerical rank
+  /// - rtol: s_i <= rtol * s_0 is treated as 0
+  pub fn rank(&self, rtol: F) -> usize {
+    let t = rtol * self.s.first().map_or(<F>::from(0).unwrap(), |s| s.abs());
+    self.s.iter().filter(|s| s.abs() > t).count()
+  }
+  /// Moore-Penrose pseudo inverse V diag(1/s) U^T (cols x rows)
+  /// - rtol: s_i <= rtol * s_0 is treated as 0
+  pub fn pinv(&self, rtol: F) -> Vec<Vec<F>> {
+    let o = <F>::from(0).unwrap();
+    let r = self.rank(rtol);
+    (0..self.vt[0].len()).map(|j|
+      (0..self.u.len()).map(|i|
+        (0..r).fold(o, |a, k|
+          a + self.vt[k][j] * self.u[i][k] / self.s[k])
+      ).collect()
+    ).collect()
+  }
+  /// orthonormal basis vectors of the null space (each has length cols)
+  /// - rtol: s_i <= rtol * s_0 is treated as 0
+  pub fn null_space(&self, rtol: F) -> Vec<Vec<F>> {
+    let n = self.vt[0].len();
+    let mut b = self.vt.clone();
+    while b.len() < n { b.push(complement(&b, n)); } // rows < cols
+    b.split_off(self.rank(rtol))
+  }
+  /// condition number (2-norm) s_max / s_min (infinity when singular)
+  pub fn cond(&self) -> F {
+    let o = <F>::from(0).unwrap();
+    let (mx, mn) = self.s.iter().fold((o, <F>::infinity()), |(mx, mn), s|
+      (mx.max(s.abs()), mn.min(s.abs())));
+    if mn == o { <F>::infinity() } else { mx / mn }
+  }
+  /// polar decomposition m = R S (returns (R, S), use new_rot)
+  /// - R = U V^T, S = V diag(s) V^T
+  pub fn polar(&self) -> Polar<F> {
+    let o = <F>::from(0).unwrap();
+    let n = self.s.len();
+    let vt = &self.vt;
+    let s = (0..n).map(|j| (0..n).map(|i|
+      (0..n).fold(o, |a, k| a + vt[k][j] * self.s[k] * vt[k][i])
+    ).collect()).collect();
+    (mul_unchecked(&self.u, vt), s)
+  }
+  /// reconstruct U diag(s) V^T
+  pub fn compose(&self) -> Vec<Vec<F>> {
+    let o = <F>::from(0).unwrap();
+    self.u.iter().map(|r| (0..self.vt[0].len()).map(|i|
+      (0..self.s.len()).fold(o, |a, k|
+        a + r[k] * self.s[k] * self.vt[k][i])
+    ).collect()).collect()
+  }
+}
+
+/// unit vector orthogonal to all orthonormal vectors in b (length n)
+fn complement<F: Float + core::fmt::Debug>(b: &[Vec<F>], n: usize) ->
+  Vec<F> {
+  let o = <F>::from(0).unwrap();
+  let l = <F>::from(1).unwrap();
+  let (d, x) = (0..n).map(|e| {
+    let mut x = (0..n).map(|i|
+      if i == e { l } else { o }).collect::<Vec<_>>();
+    for w in b.iter() {
+      let d = w[e];
+      for i in 0..n { x[i] = x[i] - d * w[i]; }
+    }
+    let d = x.iter().fold(o, |s, &a| s + a * a).sqrt();
+    (d, x)
+  }).fold((o, vec![o; n]), |a, b| if b.0 > a.0 { b } else { a });
+  x.iter().map(|&a| a / d).collect()
+}
@@ -0,0 +1,145 @@
+//! convention marked transform
+//!
+//! - storage is always rows of arrays (m\[j\]\[i\] is row j col i)
+//! - the marker is the vector convention (v' = M v or v' = v M)
+//! - a ColVec matrix stored column major is the RowVec matrix stored row major
+
+use core::marker::PhantomData;
+
+use num::Float;
+
+use crate::e::Result;
+use crate::v::v3::Vector3;
+use crate::q::Quaternion;
+use crate::m::{TMatrix, Conv, mn::{transposed, mul}, m3::Matrix3, m4::{TM4, Matrix4}};
+use crate::m::affine::TAffine;
+use crate::m::cam::{TCam, Proj, Hand, Depth};
+
+/// TConv (vector convention marker)
+pub trait TConv {
+  /// runtime value of the marker
+  const CONV: Conv;
+}
+
+/// ColVec (column vector v' = M v, OpenGL)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ColVec;
+
+/// RowVec (row vector v' = v M, DirectX)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RowVec;
+
+/// TConv for ColVec
+impl TConv for ColVec {
+  const CONV: Conv = Conv::Col;
+}
+
+/// TConv for RowVec
+impl TConv for RowVec {
+  const CONV: Conv = Conv::Row;
+}
+
+/// Xform (Matrix4 with the convention C in the type)
+/// - no From (conversions are explicit by new, into_inner, to_conv)
+/// - mixing conventions does not compile
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Xform<F, C: TConv> {
+  m: Matrix4<F>,
+  c: PhantomData<C>,
+}
+
+/// Xform
+impl<F: Float + core::fmt::Debug + core::iter::Sum, C: TConv> Xform<F, C> {
+  /// constructor (m must follow the convention C, not checked)
+  pub fn new(m: Matrix4<F>) -> Self {
+    Xform{m, c: PhantomData}
+  }
+  /// identity
+  pub fn identity() -> Self {
+    Self::new(Matrix4::identity())
+  }
+  /// like as Matrix4
+  pub fn m(&self) -> &Matrix4<F> {
+    &self.m
+  }
+  /// Matrix4 (move)
+  pub fn into_inner(self) -> Matrix4<F> {
+    self.m
+  }
+  /// runtime convention
+  pub fn conv(&self) -> Conv {
+    C::CONV
+  }
+  /// convert to the convention D (transpose when different)
+  pub fn to_conv<D: TConv>(&self) -> Xform<F, D> {
+    Xform::new(if C::CONV == D::CONV { self.m } else { transposed(&self.m) })
+  }
+  /// self then b (b is applied after self in both conventions)
+  pub fn then(&self, b: &Self) -> Self {
+    Self::new(match C::CONV {
+    Conv::Col => mul(&b.m, &self.m),
+    Conv::Row => mul(&self.m, &b.m)
+    })
+  }
+  /// translation
+  pub fn translate(t: &Vector3<F>) -> Self {
+    Self::new(Matrix4::translate(t, C::CONV))
+  }
+  /// non uniform scale
+  pub fn scale(s: &Vector3<F>) -> Self {
+    Self::new(Matrix4::scale(s))
+  }
+  /// rotation from Quaternion \[w, x, y, z\] (normalized)
+  pub fn rot_q(q: &Quaternion<F>) -> Self {
+    Self::new(Matrix4::rot_q(q, C::CONV))
+  }
+  /// rotation from Quaternion by Matrix4::from_rot (same as rot_q)
+  /// - from_rot is in column vector form, transposed for RowVec
+  pub fn from_rot(q: &Quaternion<F>) -> Self {
+    Xform::<F, ColVec>::new(Matrix4::from_rot(q)).to_conv()
+  }
+  /// translation * rotation * scale (scale first, translation last)
+  pub fn trs(t: &Vector3<F>, q: &Quaternion<F>, s: &Vector3<F>) -> Self {
+    Self::new(Matrix4::trs(t, q, s, C::CONV))
+  }
+  /// view matrix
+  pub fn look_at(eye: &Vector3<F>, center: &Vector3<F>, up: &Vector3<F>,
+    hand: Hand) -> Self {
+    Self::new(Matrix4::look_at(eye, center, up, proj::<C>(hand, Depth::default())))
+  }
+  /// perspective from field of view
+  pub fn perspective(fovy: F, aspect: F, near: F, far: F, hand: Hand,
+    depth: Depth) -> Self {
+    Self::new(Matrix4::perspective(fovy, aspect, near, far, proj::<C>(hand, depth)))
+  }
+  /// decompose into (translation, rotation, scale)
+  pub fn decompose(&self) -> Result<(Vector3<F>, Quaternion<F>, Vector3<F>)> {
+    self.m.decompose(C::CONV)
+  }
+  /// inverse (rigid, affine or general detected with e)
+  pub fn inverse(&self, e: F) -> Result<Self> {
+    Ok(Self::new(self.m.inverse(C::CONV, e)?))
+  }
+  /// normal matrix (same convention)
+  pub fn normal_matrix(&self) -> Result<Matrix3<F>> {
+    self.m.normal_matrix(C::CONV)
+  }
+  /// transform point (w = 1, divided by the result w)
+  /// - error when the result w is 0
+  pub fn transform_point(&self, v: &Vector3<F>) -> Result<Vector3<F>> {
+    self.m.transform_point(v, C::CONV)
+  }
+  /// transform direction (w = 0)
+  pub fn transform_vector(&self, v: &Vector3<F>) -> Vector3<F> {
+    self.m.transform_vector(v, C::CONV)
+  }
+  /// check equal with precision
+  pub fn prec_eq(&self, e: F, b: &Self) -> bool {
+    self.m.prec_eq(e, &b.m)
+  }
+}
+
+/// Proj of the convention C
+fn proj<C: TConv>(hand: Hand, depth: Depth) -> Proj {
+  Proj{hand, depth, conv: C::CONV}
+}
@@ -3,11 +3,11 @@
 
 use num::Float;
 
-use crate::v::{TVector, v3::Vector3};
+use crate::v::v3::Vector3;
 use crate::m::{TMatrix, m4::{TM4, Matrix4}};
 
 /// TQuaternion
-pub trait TQuaternion<F: Float + std::fmt::Debug> {
+pub trait TQuaternion<F: Float + core::fmt::Debug> {
   /// constructor
   fn from_axis_and_angle(v: &Vector3<F>, a: F) -> Self;
   /// constructor
@@ -23,6 +23,7 @@ pub trait TQuaternion<F: Float + std::fmt::Debug> {
   /// to Matrix4 rot
   /// - q * r * q.conjugate()
   /// - Matrix4::from_p_right(q.conjugate()) * Matrix4::from_q_left(q) * r
+  /// - column vector form on r \[x, y, z, w\] (transpose it for Conv::Row)
   fn to_m4_rot(&self) -> Matrix4<F>;
 }
 
@@ -30,19 +31,19 @@ pub trait TQuaternion<F: Float + std::fmt::Debug> {
 pub type Quaternion<F> = [F; 4];
 
 /// TQuaternion for Quaternion
-impl<F: Float + std::fmt::Debug + std::iter::Sum> TQuaternion<F> for Quaternion<F> {
+impl<F: Float + core::fmt::Debug + core::iter::Sum> TQuaternion<F> for Quaternion<F> {
   /// constructor
   fn from_axis_and_angle(v: &Vector3<F>, a: F) -> Self {
     let t = a / <F>::from(2.0).unwrap();
     let d = v.iter().map(|&p| p * p).sum::<F>().sqrt();
-    let n = v.iter().map(|&p| p / d).collect::<Vec<_>>();
-    [t.cos(), n[0] * t.sin(), n[1] * t.sin(), n[2] * t.sin()]
+    let s = t.sin() / d;
+    [t.cos(), v[0] * s, v[1] * s, v[2] * s]
   }
   /// constructor
   fn identity() -> Self {
-    Self::new(&(0..4).into_iter().map(|i|
-      <F>::from(if i == 0 { 1.0 } else { 0.0 }).unwrap()
-    ).collect::<Vec<_>>())
+    let o = <F>::from(0).unwrap();
+    let l = <F>::from(1).unwrap();
+    [l, o, o, o]
   }
   /// conjugate
   fn conjugate(&self) -> Self {
@@ -63,6 +64,7 @@ impl<F: Float + std::fmt::Debug + std::iter::Sum> TQuaternion<F> for Quaternion<
   /// to Matrix4 rot
   /// - q * r * q.conjugate()
   /// - Matrix4::from_p_right(q.conjugate()) * Matrix4::from_q_left(q) * r
+  /// - column vector form on r \[x, y, z, w\] (transpose it for Conv::Row)
   fn to_m4_rot(&self) -> Matrix4<F> {
     let m4p = Matrix4::from_p_right(&self.conjugate());
     let m4q = Matrix4::from_q_left(self);
@@ -0,0 +1,164 @@
+//! tolerance
+//!
+
+use num::Float;
+
+use crate::v::vn::Vector;
+use crate::w::{Vect, Mat, Quat};
+
+/// Tol (comparison mode)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tol<F> {
+  /// |a - b| < e
+  Abs(F),
+  /// |a - b| <= r max(|a|, |b|)
+  Rel(F),
+  /// |a - b| <= n ulp (unit in the last place of max(|a|, |b|))
+  Ulp(u32),
+  /// |a - b| < e or |a - b| <= r max(|a|, |b|) (e, r)
+  /// - e works near 0 where Rel fails, r works for large values
+  Mixed(F, F),
+}
+
+/// Tol from F (Abs)
+impl<F: Float> From<F> for Tol<F> {
+  /// Tol::Abs(e)
+  fn from(e: F) -> Self { Tol::Abs(e) }
+}
+
+/// Tol
+impl<F: Float> Tol<F> {
+  /// check a and b are equal within the tolerance
+  /// - NaN is never equal, same infinities are equal
+  pub fn check(&self, a: F, b: F) -> bool {
+    if a == b { return true; }
+    let d = (a - b).abs();
+    let m = a.abs().max(b.abs());
+    match *self {
+    Tol::Abs(e) => d < e,
+    Tol::Rel(r) => d <= r * m,
+    Tol::Ulp(n) => d <= <F>::from(n).unwrap() * ulp(m),
+    Tol::Mixed(e, r) => d < e || d <= r * m
+    }
+  }
+}
+
+/// unit in the last place of x (the smallest subnormal at least)
+pub fn ulp<F: Float>(x: F) -> F {
+  let (_, e, _) = x.integer_decode();
+  let t = <F>::from(2).unwrap().powi(e as i32);
+  t.max(<F>::min_positive_value() * <F>::epsilon())
+}
+
+/// TolEq (element wise comparison for scalars, vectors, matrices)
+pub trait TolEq {
+  /// element type
+  type E: Float;
+  /// check all elements are equal within t
+  fn tol_eq(&self, b: &Self, t: Tol<Self::E>) -> bool;
+  /// write element wise difference self - b
+  fn fmt_diff(&self, b: &Self, f: &mut core::fmt::Formatter<'_>) ->
+    core::fmt::Result;
+}
+
+/// TolEq for f32 f64
+macro_rules! tol_eq_float {
+  ($($f: ty),*) => {$(
+    impl TolEq for $f {
+      type E = $f;
+      /// check all elements are equal within t
+      fn tol_eq(&self, b: &Self, t: Tol<$f>) -> bool { t.check(*self, *b) }
+      /// write element wise difference self - b
+      fn fmt_diff(&self, b: &Self, f: &mut core::fmt::Formatter<'_>) ->
+        core::fmt::Result {
+        write!(f, "{:e}", self - b)
+      }
+    }
+  )*}
+}
+
+tol_eq_float!(f32, f64);
+
+/// TolEq for slice (nested for matrices)
+impl<T: TolEq> TolEq for [T] {
+  type E = T::E;
+  /// check all elements are equal within t
+  fn tol_eq(&self, b: &Self, t: Tol<T::E>) -> bool {
+    self.len() == b.len() && self.iter().zip(b.iter()).all(|(p, q)| p.tol_eq(q, t))
+  }
+  /// write element wise difference self - b
+  fn fmt_diff(&self, b: &Self, f: &mut core::fmt::Formatter<'_>) ->
+    core::fmt::Result {
+    if self.len() != b.len() {
+      return write!(f, "(length {} != {})", self.len(), b.len());
+    }
+    write!(f, "[")?;
+    for (i, (p, q)) in self.iter().zip(b.iter()).enumerate() {
+      if i > 0 { write!(f, ", ")?; }
+      p.fmt_diff(q, f)?;
+    }
+    write!(f, "]")
+  }
+}
+
+/// TolEq for Vector (Matrix is Vector of Vector)
+impl<T: TolEq, const N: usize> TolEq for Vector<T, N> {
+  type E = T::E;
+  /// check all elements are equal within t
+  fn tol_eq(&self, b: &Self, t: Tol<T::E>) -> bool {
+    self[..].tol_eq(&b[..], t)
+  }
+  /// write element wise difference self - b
+  fn fmt_diff(&self, b: &Self, f: &mut core::fmt::Formatter<'_>) ->
+    core::fmt::Result {
+    self[..].fmt_diff(&b[..], f)
+  }
+}
+
+/// TolEq for Vec
+#[cfg(feature = "alloc")]
+impl<T: TolEq> TolEq for alloc::vec::Vec<T> {
+  type E = T::E;
+  /// check all elements are equal within t
+  fn tol_eq(&self, b: &Self, t: Tol<T::E>) -> bool {
+    self[..].tol_eq(&b[..], t)
+  }
+  /// write element wise difference self - b
+  fn fmt_diff(&self, b: &Self, f: &mut core::fmt::Formatter<'_>) ->
+    core::fmt::Result {
+    self[..].fmt_diff(&b[..], f)
+  }
+}
+
+/// TolEq for the wrappers
+macro_rules! tol_eq_wrap {
+  ($t: ty, [$($g: tt)*]) => {
+    impl<F: TolEq, $($g)*> TolEq for $t {
+      type E = F::E;
+      /// check all elements are equal within t
+      fn tol_eq(&self, b: &Self, t: Tol<F::E>) -> bool {
+        self.0.tol_eq(&b.0, t)
+      }
+      /// write element wise difference self - b
+      fn fmt_diff(&self, b: &Self, f: &mut core::fmt::Formatter<'_>) ->
+        core::fmt::Result {
+        self.0.fmt_diff(&b.0, f)
+      }
+    }
+  }
+}
+
+tol_eq_wrap!(Vect<F, N>, [const N: usize]);
+tol_eq_wrap!(Mat<F, R, C>, [const R: usize, const C: usize]);
+tol_eq_wrap!(Quat<F>, []);
+
+/// Diff (Display element wise difference a - b)
+pub struct Diff<'a, T: ?Sized>(pub &'a T, pub &'a T);
+
+/// Display for Diff
+impl<T: TolEq + ?Sized> core::fmt::Display for Diff<'_, T> {
+  /// write element wise difference a - b
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    self.0.fmt_diff(self.1, f)
+  }
+}
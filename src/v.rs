@@ -1,27 +1,54 @@
 //! vector
 //!
 
+pub mod vn;
 pub mod v3;
 pub mod v4;
 
 use num::Float;
 
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::e::Result;
+use crate::t::Tol;
 use crate::m::TMatrix;
 
 /// TVector
-pub trait TVector<F: Float + std::fmt::Debug> {
+/// - the other operand is Self (mismatch does not compile)
+pub trait TVector<F: Float + core::fmt::Debug> {
+  /// square matrix of the same dimension
+  type M: TMatrix<F>;
   /// constructor
-  fn new(v: &Vec<F>) -> Self;
+  /// - panics when v is too short (use try_from_slice for untrusted input)
+  fn new(v: &[F]) -> Self;
+  /// constructor from slice
+  /// - error when the length is not the dimension
+  fn try_from_slice(v: &[F]) -> Result<Self> where Self: Sized;
+  /// constructor from iterator
+  /// - error when the number of items is not the dimension
+  fn try_from_iter(it: impl IntoIterator<Item = F>) -> Result<Self>
+    where Self: Sized;
   /// check equal with precision
-  fn prec_eq(&self, e: F, v: &impl TVector<F>) -> bool;
+  fn prec_eq(&self, e: F, v: &Self) -> bool;
+  /// check equal with tolerance (Abs Rel Ulp Mixed)
+  fn prec_eq_tol(&self, t: Tol<F>, v: &Self) -> bool {
+    crate::tol_eq(self.me(), t, v.me())
+  }
   /// to_vec
+  #[cfg(feature = "alloc")]
   fn to_vec(&self) -> Vec<F>;
   /// like as slice
   fn me(&self) -> &[F];
   /// a dot self
-  fn dot(&self, a: &impl TVector<F>) -> F;
+  fn dot(&self, a: &Self) -> F;
   /// m dot self
-  fn dot_mv(&self, m: &impl TMatrix<F>) -> Self;
-  /// self cross b
-  fn cross(&self, b: &impl TVector<F>) -> Self;
+  fn dot_mv(&self, m: &Self::M) -> Self;
+  /// solve m x = self (without inv)
+  /// - p: prec (assume pivot = 0)
+  #[cfg(feature = "alloc")]
+  fn solve_mv(&self, m: &Self::M, p: F) -> Result<Self>
+    where Self: Sized {
+    Ok(Self::new(&crate::m::solve(&m.to_vec(), &self.to_vec(), p)?))
+  }
 }
@@ -3,58 +3,31 @@
 
 use num::Float;
 
-use crate::v::TVector;
-use crate::m::TMatrix;
+use crate::e::{Error, Result};
+use crate::v::{vn::Vector, v3::Vector3};
+
+/// TV4 (methods defined only for Vector4)
+pub trait TV4<F: Float + core::fmt::Debug> {
+  /// xyz (w is dropped)
+  fn to_v3(&self) -> Vector3<F>;
+  /// homogeneous divide (xyz / w)
+  /// - error when w is 0 (tiny w still gives huge values)
+  fn div_w(&self) -> Result<Vector3<F>>;
+}
 
 /// Vector4
-pub type Vector4<F> = [F; 4];
+pub type Vector4<F> = Vector<F, 4>;
 
-/// TVector for Vector4
-impl<F: Float + std::fmt::Debug + std::iter::Sum> TVector<F> for Vector4<F> {
-  /// constructor
-  fn new(v: &Vec<F>) -> Self {
-    (0..4).into_iter().map(|i| v[i]).collect::<Vec<_>>().try_into().unwrap()
-  }
-  /// check equal with precision
-  fn prec_eq(&self, e: F, v: &impl TVector<F>) -> bool {
-    for i in 0..4 {
-      if (self[i] - v.me()[i]).abs() >= e { return false; }
-    }
-    true
-  }
-  /// to_vec
-  fn to_vec(&self) -> Vec<F> {
-    self.iter().map(|&f| f).collect::<Vec<_>>()
-  }
-  /// like as slice
-  fn me(&self) -> &[F] {
-    self
-  }
-  /// a dot self
-  fn dot(&self, a: &impl TVector<F>) -> F {
-    let a = a.me();
-    (0..4).into_iter().map(|i| a[i] * self[i]).sum::<F>()
-  }
-  /// m dot self
-  fn dot_mv(&self, m: &impl TMatrix<F>) -> Self {
-    Self::new(&(0..4).into_iter().map(|j| self.dot(&m.rowv4(j))).collect())
+/// TV4 for Vector4
+impl<F: Float + core::fmt::Debug> TV4<F> for Vector4<F> {
+  /// xyz (w is dropped)
+  fn to_v3(&self) -> Vector3<F> {
+    [self[0], self[1], self[2]]
   }
-  /// self cross b
-  fn cross(&self, _b: &impl TVector<F>) -> Self {
-/*
-    let a = self.me();
-    let b = b.me();
-    let o = <F>::from(0).unwrap();
-    Vector8::<F>::new(&vec![
-      a[1] * b[2] - a[2] * b[1],
-      a[2] * b[0] - a[0] * b[2],
-      a[0] * b[1] - a[1] * b[0],
-      o,
-      a[0] * b[3] - a[3] * b[0],
-      a[1] * b[3] - a[3] * b[1],
-      a[2] * b[3] - a[3] * b[2],
-      o])
-*/
-    panic!("TODO: v4 x v4 cross generates v8");
+  /// homogeneous divide (xyz / w)
+  /// - error when w is 0 (tiny w still gives huge values)
+  fn div_w(&self) -> Result<Vector3<F>> {
+    if self[3] == <F>::from(0).unwrap() { return Err(Error::AtInfinity); }
+    Ok([self[0] / self[3], self[1] / self[3], self[2] / self[3]])
   }
 }
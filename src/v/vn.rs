@@ -0,0 +1,77 @@
+//! vectorN
+//!
+
+use num::Float;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::e::{Error, Result};
+use crate::v::TVector;
+use crate::m::mn::{Matrix, mul_mv};
+
+/// Vector (const generic N)
+pub type Vector<F, const N: usize> = [F; N];
+
+/// TVector for Vector
+impl<F: Float + core::fmt::Debug + core::iter::Sum, const N: usize> TVector<F>
+  for Vector<F, N> {
+  /// square matrix of the same dimension
+  type M = Matrix<F, N, N>;
+  /// constructor
+  fn new(v: &[F]) -> Self {
+    core::array::from_fn(|i| v[i])
+  }
+  /// constructor from slice
+  fn try_from_slice(v: &[F]) -> Result<Self> {
+    try_from_slice(v)
+  }
+  /// constructor from iterator
+  fn try_from_iter(it: impl IntoIterator<Item = F>) -> Result<Self> {
+    try_from_iter(it)
+  }
+  /// check equal with precision
+  fn prec_eq(&self, e: F, v: &Self) -> bool {
+    for i in 0..N {
+      if (self[i] - v[i]).abs() >= e { return false; }
+    }
+    true
+  }
+  /// to_vec
+  #[cfg(feature = "alloc")]
+  fn to_vec(&self) -> Vec<F> {
+    self[..].to_vec()
+  }
+  /// like as slice
+  fn me(&self) -> &[F] {
+    self
+  }
+  /// a dot self
+  fn dot(&self, a: &Self) -> F {
+    (0..N).map(|i| a[i] * self[i]).sum::<F>()
+  }
+  /// m dot self
+  fn dot_mv(&self, m: &Matrix<F, N, N>) -> Self {
+    mul_mv(m, self)
+  }
+}
+
+/// from slice
+/// - error when the length is not N
+pub fn try_from_slice<F: Float, const N: usize>(v: &[F]) ->
+  Result<Vector<F, N>> {
+  if v.len() != N {
+    return Err(Error::DimensionMismatch{expected: N, actual: v.len()});
+  }
+  Ok(core::array::from_fn(|i| v[i]))
+}
+
+/// from iterator
+/// - error when the number of items is not N (all items are counted)
+pub fn try_from_iter<F: Float, const N: usize>(
+  it: impl IntoIterator<Item = F>) -> Result<Vector<F, N>> {
+  let mut v = [<F>::from(0).unwrap(); N];
+  let n = it.into_iter().fold(0, |n, x| { if n < N { v[n] = x; } n + 1 });
+  if n != N { return Err(Error::DimensionMismatch{expected: N, actual: n}); }
+  Ok(v)
+}
@@ -0,0 +1,435 @@
+//! newtype wrappers with operators
+//!
+//! - operators are in mathematical order (a * b is a dot b, m * v is m dot v)
+//! - convert freely from and to the plain array aliases (From, Deref)
+
+use core::ops::{Add, Sub, Neg, Mul, Div, Index, IndexMut};
+use core::ops::{AddAssign, SubAssign, MulAssign, DivAssign};
+use core::ops::{Deref, DerefMut};
+
+use num::Float;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::e::Error;
+use crate::v::vn::{self, Vector};
+use crate::q::Quaternion;
+use crate::m::{TMatrix, mn::{self, Matrix, mul, mul_mv}};
+
+/// Vect (wraps Vector)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Vect<F, const N: usize>(pub Vector<F, N>);
+
+/// Mat (wraps Matrix, R rows x C cols)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mat<F, const R: usize, const C: usize>(pub Matrix<F, R, C>);
+
+/// Quat (wraps Quaternion \[w, x, y, z\])
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quat<F>(pub Quaternion<F>);
+
+/// Vect3
+pub type Vect3<F> = Vect<F, 3>;
+/// Vect4
+pub type Vect4<F> = Vect<F, 4>;
+/// Mat3
+pub type Mat3<F> = Mat<F, 3, 3>;
+/// Mat4
+pub type Mat4<F> = Mat<F, 4, 4>;
+
+/// element wise map and zip (used by the operators)
+trait Elementwise<F: Float>: Copy {
+  /// g(a)
+  fn map(self, g: impl Fn(F) -> F) -> Self;
+  /// g(a, b)
+  fn zip(self, b: Self, g: impl Fn(F, F) -> F) -> Self;
+}
+
+/// Elementwise for Vect
+impl<F: Float, const N: usize> Elementwise<F> for Vect<F, N> {
+  /// g(a)
+  fn map(self, g: impl Fn(F) -> F) -> Self {
+    Vect(core::array::from_fn(|i| g(self.0[i])))
+  }
+  /// g(a, b)
+  fn zip(self, b: Self, g: impl Fn(F, F) -> F) -> Self {
+    Vect(core::array::from_fn(|i| g(self.0[i], b.0[i])))
+  }
+}
+
+/// Elementwise for Mat
+impl<F: Float, const R: usize, const C: usize> Elementwise<F>
+  for Mat<F, R, C> {
+  /// g(a)
+  fn map(self, g: impl Fn(F) -> F) -> Self {
+    Mat(core::array::from_fn(|j| core::array::from_fn(|i| g(self.0[j][i]))))
+  }
+  /// g(a, b)
+  fn zip(self, b: Self, g: impl Fn(F, F) -> F) -> Self {
+    Mat(core::array::from_fn(|j| core::array::from_fn(|i|
+      g(self.0[j][i], b.0[j][i]))))
+  }
+}
+
+/// Elementwise for Quat
+impl<F: Float> Elementwise<F> for Quat<F> {
+  /// g(a)
+  fn map(self, g: impl Fn(F) -> F) -> Self {
+    Quat(core::array::from_fn(|i| g(self.0[i])))
+  }
+  /// g(a, b)
+  fn zip(self, b: Self, g: impl Fn(F, F) -> F) -> Self {
+    Quat(core::array::from_fn(|i| g(self.0[i], b.0[i])))
+  }
+}
+
+/// element wise operators (+ - and scalar * /) for a wrapper
+macro_rules! elementwise {
+  ($t: ty, [$($g: tt)*]) => {
+    impl<F: Float, $($g)*> Add for $t {
+      type Output = Self;
+      /// a + b
+      fn add(self, b: Self) -> Self { self.zip(b, |a, b| a + b) }
+    }
+    impl<F: Float, $($g)*> Sub for $t {
+      type Output = Self;
+      /// a - b
+      fn sub(self, b: Self) -> Self { self.zip(b, |a, b| a - b) }
+    }
+    impl<F: Float, $($g)*> Neg for $t {
+      type Output = Self;
+      /// -a
+      fn neg(self) -> Self { self.map(|a| -a) }
+    }
+    impl<F: Float, $($g)*> Mul<F> for $t {
+      type Output = Self;
+      /// a * s
+      fn mul(self, s: F) -> Self { self.map(|a| a * s) }
+    }
+    impl<F: Float, $($g)*> Div<F> for $t {
+      type Output = Self;
+      /// a / s
+      fn div(self, s: F) -> Self { self.map(|a| a / s) }
+    }
+    impl<F: Float, $($g)*> AddAssign for $t {
+      /// a += b
+      fn add_assign(&mut self, b: Self) { *self = *self + b; }
+    }
+    impl<F: Float, $($g)*> SubAssign for $t {
+      /// a -= b
+      fn sub_assign(&mut self, b: Self) { *self = *self - b; }
+    }
+    impl<F: Float, $($g)*> MulAssign<F> for $t {
+      /// a *= s
+      fn mul_assign(&mut self, s: F) { *self = *self * s; }
+    }
+    impl<F: Float, $($g)*> DivAssign<F> for $t {
+      /// a /= s
+      fn div_assign(&mut self, s: F) { *self = *self / s; }
+    }
+  }
+}
+
+elementwise!(Vect<F, N>, [const N: usize]);
+elementwise!(Mat<F, R, C>, [const R: usize, const C: usize]);
+elementwise!(Quat<F>, []);
+
+/// a dot b (Vect)
+impl<F: Float + core::iter::Sum, const N: usize> Vect<F, N> {
+  /// a dot b
+  pub fn dot(&self, b: &Self) -> F {
+    (0..N).map(|i| self.0[i] * b.0[i]).sum::<F>()
+  }
+}
+
+/// a cross b (Vect3)
+impl<F: Float> Vect<F, 3> {
+  /// a cross b
+  pub fn cross(&self, b: &Self) -> Self {
+    let (a, b) = (self.0, b.0);
+    Vect([
+      a[1] * b[2] - a[2] * b[1],
+      a[2] * b[0] - a[0] * b[2],
+      a[0] * b[1] - a[1] * b[0]])
+  }
+}
+
+/// a * b (R x K * K x C = R x C)
+impl<F: Float + core::fmt::Debug + core::iter::Sum,
+  const R: usize, const K: usize, const C: usize> Mul<Mat<F, K, C>>
+  for Mat<F, R, K> {
+  type Output = Mat<F, R, C>;
+  /// a * b (R x K * K x C = R x C)
+  fn mul(self, b: Mat<F, K, C>) -> Mat<F, R, C> {
+    Mat(mul(&self.0, &b.0))
+  }
+}
+
+/// m * v (R x C * C = R)
+impl<F: Float + core::fmt::Debug + core::iter::Sum,
+  const R: usize, const C: usize> Mul<Vect<F, C>> for Mat<F, R, C> {
+  type Output = Vect<F, R>;
+  /// m * v (R x C * C = R)
+  fn mul(self, v: Vect<F, C>) -> Vect<F, R> {
+    Vect(mul_mv(&self.0, &v.0))
+  }
+}
+
+/// a *= b (a = a * b, square)
+impl<F: Float + core::fmt::Debug + core::iter::Sum, const N: usize>
+  MulAssign<Mat<F, N, N>> for Mat<F, N, N> {
+  /// a *= b (a = a * b, square)
+  fn mul_assign(&mut self, b: Self) { *self = *self * b; }
+}
+
+/// square Mat
+impl<F: Float + core::fmt::Debug + core::iter::Sum, const N: usize>
+  Mat<F, N, N> {
+  /// identity
+  pub fn identity() -> Self {
+    Mat(Matrix::<F, N, N>::identity())
+  }
+  /// transpose
+  pub fn transpose(&self) -> Self {
+    Mat(self.0.transpose())
+  }
+  /// det
+  pub fn det(&self) -> F {
+    self.0.det()
+  }
+  /// inv
+  /// - p: prec (assume det = 0)
+  pub fn inv(&self, p: F) -> Option<Self> {
+    self.0.inv(p).map(Mat)
+  }
+}
+
+/// a * b (Hamilton product)
+impl<F: Float> Mul for Quat<F> {
+  type Output = Self;
+  /// a * b (Hamilton product)
+  fn mul(self, b: Self) -> Self {
+    let (a, b) = (self.0, b.0);
+    Quat([
+      a[0] * b[0] - a[1] * b[1] - a[2] * b[2] - a[3] * b[3],
+      a[0] * b[1] + a[1] * b[0] + a[2] * b[3] - a[3] * b[2],
+      a[0] * b[2] - a[1] * b[3] + a[2] * b[0] + a[3] * b[1],
+      a[0] * b[3] + a[1] * b[2] - a[2] * b[1] + a[3] * b[0]])
+  }
+}
+
+/// a *= b (a = a * b, Hamilton product)
+impl<F: Float> MulAssign for Quat<F> {
+  /// a *= b (a = a * b, Hamilton product)
+  fn mul_assign(&mut self, b: Self) { *self = *self * b; }
+}
+
+/// Quat
+impl<F: Float> Quat<F> {
+  /// identity
+  pub fn identity() -> Self {
+    let o = <F>::from(0).unwrap();
+    let l = <F>::from(1).unwrap();
+    Quat([l, o, o, o])
+  }
+  /// conjugate
+  pub fn conjugate(&self) -> Self {
+    let q = self.0;
+    Quat([q[0], -q[1], -q[2], -q[3]])
+  }
+  /// rotate v (q * v * q.conjugate())
+  pub fn rotate(&self, v: &Vect<F, 3>) -> Vect<F, 3> {
+    let o = <F>::from(0).unwrap();
+    let r = *self * Quat([o, v[0], v[1], v[2]]) * self.conjugate();
+    Vect([r[1], r[2], r[3]])
+  }
+}
+
+/// conversions from and to the plain arrays (From Deref AsRef)
+macro_rules! wrap {
+  ($t: ty, $a: ty, [$($g: tt)*]) => {
+    impl<F, $($g)*> From<$a> for $t {
+      /// wrap
+      fn from(a: $a) -> Self { Self(a) }
+    }
+    impl<F, $($g)*> From<$t> for $a {
+      /// unwrap
+      fn from(w: $t) -> Self { w.0 }
+    }
+    impl<F, $($g)*> Deref for $t {
+      type Target = $a;
+      /// like as the array
+      fn deref(&self) -> &$a { &self.0 }
+    }
+    impl<F, $($g)*> DerefMut for $t {
+      /// like as the mutable array
+      fn deref_mut(&mut self) -> &mut $a { &mut self.0 }
+    }
+    impl<F, $($g)*> AsRef<$a> for $t {
+      /// like as the array
+      fn as_ref(&self) -> &$a { &self.0 }
+    }
+  }
+}
+
+wrap!(Vect<F, N>, Vector<F, N>, [const N: usize]);
+wrap!(Mat<F, R, C>, Matrix<F, R, C>, [const R: usize, const C: usize]);
+wrap!(Quat<F>, Quaternion<F>, []);
+
+/// Vect from slice (error when the length is not N)
+impl<F: Float, const N: usize> TryFrom<&[F]> for Vect<F, N> {
+  type Error = Error;
+  /// Vect from slice (error when the length is not N)
+  fn try_from(v: &[F]) -> Result<Self, Error> {
+    vn::try_from_slice(v).map(Vect)
+  }
+}
+
+/// Vect from Vec (error when the length is not N)
+#[cfg(feature = "alloc")]
+impl<F: Float, const N: usize> TryFrom<Vec<F>> for Vect<F, N> {
+  type Error = Error;
+  /// Vect from Vec (error when the length is not N)
+  fn try_from(v: Vec<F>) -> Result<Self, Error> {
+    vn::try_from_slice(&v).map(Vect)
+  }
+}
+
+/// Quat from slice (error when the length is not 4)
+impl<F: Float> TryFrom<&[F]> for Quat<F> {
+  type Error = Error;
+  /// Quat from slice (error when the length is not 4)
+  fn try_from(q: &[F]) -> Result<Self, Error> {
+    vn::try_from_slice(q).map(Quat)
+  }
+}
+
+/// Quat from Vec (error when the length is not 4)
+#[cfg(feature = "alloc")]
+impl<F: Float> TryFrom<Vec<F>> for Quat<F> {
+  type Error = Error;
+  /// Quat from Vec (error when the length is not 4)
+  fn try_from(q: Vec<F>) -> Result<Self, Error> {
+    vn::try_from_slice(&q).map(Quat)
+  }
+}
+
+/// Mat from flat slice (row major, error when the length is not R * C)
+impl<F: Float + core::fmt::Debug, const R: usize, const C: usize>
+  TryFrom<&[F]> for Mat<F, R, C> {
+  type Error = Error;
+  /// Mat from flat slice (row major, error when the length is not R * C)
+  fn try_from(m: &[F]) -> Result<Self, Error> {
+    mn::try_from_slice(m).map(Mat)
+  }
+}
+
+/// Mat from rows (error when the number of rows or cols is not R or C)
+#[cfg(feature = "alloc")]
+impl<F: Float + core::fmt::Debug, const R: usize, const C: usize>
+  TryFrom<&Vec<Vec<F>>> for Mat<F, R, C> {
+  type Error = Error;
+  /// Mat from rows (error when the number of rows or cols is not R or C)
+  fn try_from(m: &Vec<Vec<F>>) -> Result<Self, Error> {
+    mn::from_vec(m).map(Mat)
+  }
+}
+
+/// Mat from rows (error when the number of rows or cols is not R or C)
+#[cfg(feature = "alloc")]
+impl<F: Float + core::fmt::Debug, const R: usize, const C: usize>
+  TryFrom<Vec<Vec<F>>> for Mat<F, R, C> {
+  type Error = Error;
+  /// Mat from rows (error when the number of rows or cols is not R or C)
+  fn try_from(m: Vec<Vec<F>>) -> Result<Self, Error> {
+    mn::from_vec(&m).map(Mat)
+  }
+}
+
+/// from iterator (Vect)
+impl<F: Float, const N: usize> Vect<F, N> {
+  /// from iterator
+  /// - error when the number of items is not N
+  pub fn try_from_iter(it: impl IntoIterator<Item = F>) ->
+    Result<Self, Error> {
+    vn::try_from_iter(it).map(Vect)
+  }
+}
+
+/// from iterator (Quat)
+impl<F: Float> Quat<F> {
+  /// from iterator \[w, x, y, z\]
+  /// - error when the number of items is not 4
+  pub fn try_from_iter(it: impl IntoIterator<Item = F>) ->
+    Result<Self, Error> {
+    vn::try_from_iter(it).map(Quat)
+  }
+}
+
+/// from iterator (Mat)
+impl<F: Float + core::fmt::Debug, const R: usize, const C: usize>
+  Mat<F, R, C> {
+  /// from flat iterator (row major)
+  /// - error when the number of items is not R * C
+  pub fn try_from_iter(it: impl IntoIterator<Item = F>) ->
+    Result<Self, Error> {
+    mn::try_from_iter(it).map(Mat)
+  }
+}
+
+/// v\[i\]
+impl<F, const N: usize> Index<usize> for Vect<F, N> {
+  type Output = F;
+  /// v\[i\]
+  fn index(&self, i: usize) -> &F { &self.0[i] }
+}
+
+/// v\[i\]
+impl<F, const N: usize> IndexMut<usize> for Vect<F, N> {
+  /// v\[i\]
+  fn index_mut(&mut self, i: usize) -> &mut F { &mut self.0[i] }
+}
+
+/// q\[i\] (w x y z)
+impl<F> Index<usize> for Quat<F> {
+  type Output = F;
+  /// q\[i\] (w x y z)
+  fn index(&self, i: usize) -> &F { &self.0[i] }
+}
+
+/// q\[i\] (w x y z)
+impl<F> IndexMut<usize> for Quat<F> {
+  /// q\[i\] (w x y z)
+  fn index_mut(&mut self, i: usize) -> &mut F { &mut self.0[i] }
+}
+
+/// m\[j\] (row j)
+impl<F, const R: usize, const C: usize> Index<usize> for Mat<F, R, C> {
+  type Output = [F; C];
+  /// m\[j\] (row j)
+  fn index(&self, j: usize) -> &[F; C] { &self.0[j] }
+}
+
+/// m\[j\] (row j)
+impl<F, const R: usize, const C: usize> IndexMut<usize> for Mat<F, R, C> {
+  /// m\[j\] (row j)
+  fn index_mut(&mut self, j: usize) -> &mut [F; C] { &mut self.0[j] }
+}
+
+/// m\[(j, i)\] (row j col i)
+impl<F, const R: usize, const C: usize> Index<(usize, usize)>
+  for Mat<F, R, C> {
+  type Output = F;
+  /// m\[(j, i)\] (row j col i)
+  fn index(&self, (j, i): (usize, usize)) -> &F { &self.0[j][i] }
+}
+
+/// m\[(j, i)\] (row j col i)
+impl<F, const R: usize, const C: usize> IndexMut<(usize, usize)>
+  for Mat<F, R, C> {
+  /// m\[(j, i)\] (row j col i)
+  fn index_mut(&mut self, (j, i): (usize, usize)) -> &mut F {
+    &mut self.0[j][i]
+  }
+}